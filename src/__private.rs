@@ -0,0 +1,281 @@
+//! Items used by the code generated by [`Row_Ext`](crate::Row_Ext). Not
+//! public API.
+
+pub use anyhow;
+pub use futures_util::io::{AsyncRead, AsyncWrite};
+pub use tiberius;
+
+#[cfg(feature = "compress")]
+pub use crate::compress::Compressed;
+#[cfg(feature = "encoding")]
+pub use crate::encoding::Encoding;
+pub use crate::normalize::Trim;
+#[cfg(feature = "chrono-tz")]
+pub use crate::tz::{parse_tz, with_tz};
+#[cfg(feature = "chrono-tz")]
+pub use chrono_tz::Tz;
+
+pub mod convert {
+    pub use crate::convert::{from_str, nullable, try_from};
+}
+
+#[cfg(feature = "geo")]
+pub mod geo {
+    pub use crate::geo::Format;
+}
+
+pub mod params {
+    #[cfg(feature = "encoding")]
+    pub use crate::params::encode;
+    pub use crate::params::{bool_text, bytes, display, join, text, Nullable};
+    #[cfg(feature = "compress")]
+    pub use crate::params::{compress, compress_text};
+}
+
+pub mod split {
+    pub use crate::split::Split;
+}
+
+use std::fmt::Display;
+use std::str::FromStr;
+
+use ::anyhow::{Context as _, Result};
+use serde::de::DeserializeOwned;
+use tiberius::{Column, ColumnData, ColumnType};
+
+use crate::{SplitCollection, SqlDecode};
+
+/// The cells of a row. Fields are looked up by column name, or by the
+/// ordinals a [`RowMapper`](crate::RowMapper) resolved once for the result
+/// set.
+pub struct Cells<'c, 'r> {
+    cells: &'c [(&'r Column, &'r ColumnData<'static>)],
+    ordinals: Option<&'c [Option<usize>]>,
+}
+
+impl<'c, 'r> Cells<'c, 'r> {
+    pub fn new(cells: &'c [(&'r Column, &'r ColumnData<'static>)]) -> Self {
+        Cells {
+            cells,
+            ordinals: None,
+        }
+    }
+
+    pub(crate) fn with_ordinals(
+        cells: &'c [(&'r Column, &'r ColumnData<'static>)],
+        ordinals: &'c [Option<usize>],
+    ) -> Self {
+        Cells {
+            cells,
+            ordinals: Some(ordinals),
+        }
+    }
+
+    fn get(&self, field: &Field<'_>) -> Option<&'r ColumnData<'static>> {
+        match self.ordinals {
+            Some(ordinals) => ordinals
+                .get(field.index)
+                .copied()
+                .flatten()
+                .map(|i| self.cells[i].1),
+            None => self.by_name(field),
+        }
+    }
+
+    /// The cell at `index`, for types decoded by position.
+    pub(crate) fn nth(&self, index: usize) -> Option<(&'r Column, &'r ColumnData<'static>)> {
+        self.cells.get(index).copied()
+    }
+
+    fn by_name(&self, field: &Field<'_>) -> Option<&'r ColumnData<'static>> {
+        self.cells
+            .iter()
+            .find(|(col, _)| field.reads(col.name()))
+            .map(|(_, val)| *val)
+    }
+}
+
+/// Whether the row has the field's column, for `update_from_row` and
+/// `#[serde(default)]`.
+pub fn has_column(cells: &Cells<'_, '_>, field: &Field<'_>) -> bool {
+    cells.get(field).is_some()
+}
+
+/// A struct field: the column it reads and the options applied to the
+/// column's value before it is decoded. The derive emits a table of them for
+/// each struct, and the generated code only passes them to the functions
+/// below.
+pub struct Field<'a> {
+    /// The field's position in the table.
+    pub index: usize,
+    /// The field's name, for errors.
+    pub name: &'a str,
+    pub column: &'a str,
+    /// Other names of the column, from `#[serde(alias)]`.
+    pub aliases: &'a [&'a str],
+    /// Whether a column of the type can be decoded into the field, checked
+    /// once by [`RowMapper`](crate::RowMapper).
+    pub accepts: fn(ColumnType) -> bool,
+    pub trim: Option<Trim>,
+    pub empty_as_none: bool,
+    pub bool_from: Option<[&'a str; 2]>,
+    #[cfg(feature = "compress")]
+    pub compressed: Option<Compressed>,
+    #[cfg(feature = "encoding")]
+    pub encoding: Option<Encoding<'a>>,
+}
+
+impl<'a> Field<'a> {
+    pub const fn new(index: usize, name: &'a str, column: &'a str) -> Self {
+        Field {
+            index,
+            name,
+            column,
+            aliases: &[],
+            accepts: Field::any,
+            trim: None,
+            empty_as_none: false,
+            bool_from: None,
+            #[cfg(feature = "compress")]
+            compressed: None,
+            #[cfg(feature = "encoding")]
+            encoding: None,
+        }
+    }
+
+    /// Whether the field reads the column named `name`.
+    pub(crate) fn reads(&self, name: &str) -> bool {
+        self.column == name || self.aliases.contains(&name)
+    }
+
+    /// For fields whose column type is only checked when decoding.
+    pub fn any(_: ColumnType) -> bool {
+        true
+    }
+
+    /// Finds and decodes the field's column. `missing` provides the value
+    /// for a column that isn't in the row, `None` making it an error.
+    fn decode<T>(
+        &self,
+        cells: &Cells<'_, '_>,
+        missing: impl FnOnce() -> Option<T>,
+        decode: impl FnOnce(&ColumnData<'static>) -> Result<T>,
+    ) -> Result<T> {
+        match cells.get(self) {
+            Some(val) => crate::normalize::apply(val, self)
+                .and_then(|val| decode(&val))
+                .with_context(|| format!("failed to decode column `{}`", self.column)),
+            None => {
+                missing().ok_or_else(|| ::anyhow::anyhow!("column `{}` not found", self.column))
+            }
+        }
+    }
+}
+
+pub fn decode_field<T: SqlDecode>(cells: &Cells<'_, '_>, field: &Field<'_>) -> Result<T> {
+    field.decode(cells, T::missing, T::decode)
+}
+
+/// Decodes a field borrowing from the row. No `#[row_ext]` options apply.
+pub fn decode_field_ref<'r, T: crate::SqlDecodeRef<'r>>(
+    cells: &Cells<'_, 'r>,
+    field: &Field<'_>,
+) -> Result<T> {
+    match cells.get(field) {
+        Some(val) => T::decode_ref(val)
+            .with_context(|| format!("failed to decode column `{}`", field.column)),
+        None => {
+            T::missing().ok_or_else(|| ::anyhow::anyhow!("column `{}` not found", field.column))
+        }
+    }
+}
+
+/// The JSON text of a `#[row_ext(json)]` field for `ToParams`, `None` for
+/// `null`.
+pub fn json_text<T: serde::Serialize + ?Sized>(value: &T) -> Result<Option<String>> {
+    match serde_json::to_value(value)? {
+        serde_json::Value::Null => Ok(None),
+        value => Ok(Some(value.to_string())),
+    }
+}
+
+/// Collects the columns none of `fields` reads, for `#[row_ext(extra)]`.
+pub fn decode_extra<T: crate::ExtraColumns>(
+    cells: &Cells<'_, '_>,
+    fields: &[Field<'_>],
+) -> Result<T> {
+    let used = fields
+        .iter()
+        .flat_map(|f| std::iter::once(f.column).chain(f.aliases.iter().copied()))
+        .collect::<Vec<_>>();
+    let cells = cells.cells.iter().map(|(col, val)| (col.name(), *val));
+    crate::extra::collect(cells, &used)
+}
+
+pub fn decode_field_serde<T: DeserializeOwned>(
+    cells: &Cells<'_, '_>,
+    field: &Field<'_>,
+) -> Result<T> {
+    let decode = |val: Option<&ColumnData<'static>>| -> Result<T> {
+        let value = val.map_or(serde_json::Value::Null, crate::json::to_json);
+        Ok(serde_json::from_value(value)?)
+    };
+    field.decode(cells, || decode(None).ok(), |val| decode(Some(val)))
+}
+
+pub fn decode_field_json<T: DeserializeOwned>(
+    cells: &Cells<'_, '_>,
+    field: &Field<'_>,
+    path: Option<&str>,
+) -> Result<T> {
+    field.decode(
+        cells,
+        || crate::json::decode(None, path).ok(),
+        |val| crate::json::decode(Some(val), path),
+    )
+}
+
+/// Decodes the column with `convert`, which takes the column value itself.
+/// `missing` provides the value for a column that isn't in the row.
+pub fn decode_field_with<T>(
+    cells: &Cells<'_, '_>,
+    field: &Field<'_>,
+    missing: impl FnOnce() -> Option<T>,
+    convert: impl FnOnce(&ColumnData<'static>) -> Result<T>,
+) -> Result<T> {
+    field.decode(cells, missing, convert)
+}
+
+pub fn decode_field_split<C>(
+    cells: &Cells<'_, '_>,
+    field: &Field<'_>,
+    split: &split::Split<'_>,
+) -> Result<C>
+where
+    C: SplitCollection,
+    C::Item: FromStr,
+    <C::Item as FromStr>::Err: Display,
+{
+    field.decode(cells, C::missing, |val| crate::split::decode(val, split))
+}
+
+#[cfg(feature = "geo")]
+pub fn decode_field_geo<T: crate::FromGeometry>(
+    cells: &Cells<'_, '_>,
+    field: &Field<'_>,
+    format: geo::Format,
+) -> Result<T> {
+    field.decode(cells, T::missing, |val| crate::geo::decode(val, format))
+}
+
+#[cfg(feature = "xml")]
+pub fn decode_field_xml<T: DeserializeOwned>(
+    cells: &Cells<'_, '_>,
+    field: &Field<'_>,
+) -> Result<T> {
+    field.decode(
+        cells,
+        || crate::xml::decode(None).ok(),
+        |val| crate::xml::decode(Some(val)),
+    )
+}
@@ -0,0 +1,69 @@
+//! Decoding fields that borrow from the row instead of allocating.
+
+use std::borrow::Cow;
+
+use anyhow::Result;
+use tiberius::ColumnData;
+
+use crate::decode::{is_null, mismatch};
+
+/// Decodes a column value into a type borrowing from it, for structs with a
+/// lifetime deriving [`Row_Ext`](crate::Row_Ext).
+///
+/// Used for `&str`, `&[u8]` and `Option`s of them, and for fields marked
+/// `#[row_ext(borrow)]`.
+pub trait SqlDecodeRef<'r>: Sized {
+    /// Decodes the value of a column that is present in the row.
+    fn decode_ref(val: &'r ColumnData<'static>) -> Result<Self>;
+
+    /// The value to use when the column is not part of the row at all, like
+    /// [`SqlDecode::missing`](crate::SqlDecode::missing).
+    fn missing() -> Option<Self> {
+        None
+    }
+}
+
+impl<'r, T: SqlDecodeRef<'r>> SqlDecodeRef<'r> for Option<T> {
+    fn decode_ref(val: &'r ColumnData<'static>) -> Result<Self> {
+        if is_null(val) {
+            Ok(None)
+        } else {
+            T::decode_ref(val).map(Some)
+        }
+    }
+
+    fn missing() -> Option<Self> {
+        Some(None)
+    }
+}
+
+impl<'r> SqlDecodeRef<'r> for &'r str {
+    fn decode_ref(val: &'r ColumnData<'static>) -> Result<Self> {
+        match val {
+            ColumnData::String(Some(v)) => Ok(v),
+            ColumnData::Xml(Some(v)) => Ok(v.as_ref().as_ref()),
+            _ => Err(mismatch::<Self>(val)),
+        }
+    }
+}
+
+impl<'r> SqlDecodeRef<'r> for &'r [u8] {
+    fn decode_ref(val: &'r ColumnData<'static>) -> Result<Self> {
+        match val {
+            ColumnData::Binary(Some(v)) => Ok(v),
+            _ => Err(mismatch::<Self>(val)),
+        }
+    }
+}
+
+impl<'r> SqlDecodeRef<'r> for Cow<'r, str> {
+    fn decode_ref(val: &'r ColumnData<'static>) -> Result<Self> {
+        <&str>::decode_ref(val).map(Cow::Borrowed)
+    }
+}
+
+impl<'r> SqlDecodeRef<'r> for Cow<'r, [u8]> {
+    fn decode_ref(val: &'r ColumnData<'static>) -> Result<Self> {
+        <&[u8]>::decode_ref(val).map(Cow::Borrowed)
+    }
+}
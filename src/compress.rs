@@ -0,0 +1,80 @@
+//! GZip data written by SQL Server's `COMPRESS()`, enabled by the `compress`
+//! feature.
+//!
+//! `COMPRESS(N'...')` compresses the UTF-16LE bytes of `nvarchar` text and
+//! `COMPRESS('...')` the bytes of `varchar` text, so the text encoding of a
+//! `#[row_ext(compressed)]` column has to be given to read it as text.
+
+use std::io::{Read as _, Write as _};
+
+use anyhow::{anyhow, Context as _, Result};
+use flate2::{read::GzDecoder, write::GzEncoder, Compression};
+use tiberius::ColumnData;
+
+/// What the decompressed bytes of a `#[row_ext(compressed)]` column are.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Compressed {
+    /// Raw bytes, left for `Vec<u8>` fields or `encoding`.
+    Bytes,
+    /// UTF-8 text, from `COMPRESS()` of `varchar` with a UTF-8 collation.
+    Utf8,
+    /// UTF-16LE text, from `COMPRESS()` of `nvarchar`.
+    Utf16,
+}
+
+/// Decompresses a binary column into a binary or text column.
+pub(crate) fn decompress(
+    bytes: Option<&[u8]>,
+    compressed: Compressed,
+) -> Result<ColumnData<'static>> {
+    let Some(bytes) = bytes else {
+        return Ok(match compressed {
+            Compressed::Bytes => ColumnData::Binary(None),
+            Compressed::Utf8 | Compressed::Utf16 => ColumnData::String(None),
+        });
+    };
+    let mut data = Vec::new();
+    GzDecoder::new(bytes)
+        .read_to_end(&mut data)
+        .context("invalid GZip data")?;
+    Ok(match compressed {
+        Compressed::Bytes => ColumnData::Binary(Some(data.into())),
+        Compressed::Utf8 => {
+            let text = String::from_utf8(data).map_err(|_| anyhow!("invalid UTF-8 text"))?;
+            ColumnData::String(Some(text.into()))
+        }
+        Compressed::Utf16 => {
+            let invalid = || anyhow!("invalid UTF-16 text");
+            if data.len() % 2 != 0 {
+                return Err(invalid());
+            }
+            let units = data
+                .chunks_exact(2)
+                .map(|b| u16::from_le_bytes([b[0], b[1]]));
+            let text = char::decode_utf16(units)
+                .collect::<Result<String, _>>()
+                .map_err(|_| invalid())?;
+            ColumnData::String(Some(text.into()))
+        }
+    })
+}
+
+/// Compresses bytes the way `COMPRESS()` does, for writing a parameter that
+/// `DECOMPRESS()` can read back.
+pub fn compress(bytes: &[u8]) -> Vec<u8> {
+    let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+    encoder
+        .write_all(bytes)
+        .expect("writing to a Vec cannot fail");
+    encoder.finish().expect("writing to a Vec cannot fail")
+}
+
+/// Compresses text like `COMPRESS(N'...')`, as UTF-16LE, so that
+/// `CAST(DECOMPRESS(@p) AS nvarchar(max))` reads it back.
+pub fn compress_text(text: &str) -> Vec<u8> {
+    let bytes = text
+        .encode_utf16()
+        .flat_map(u16::to_le_bytes)
+        .collect::<Vec<_>>();
+    compress(&bytes)
+}
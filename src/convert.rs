@@ -0,0 +1,51 @@
+//! Conversions applied after decoding a column as an intermediate type, for
+//! `#[row_ext(from_str)]` and `#[row_ext(try_from = "...")]`.
+
+use std::{any::type_name, fmt::Debug, fmt::Display, str::FromStr};
+
+use anyhow::{anyhow, Result};
+use tiberius::ColumnData;
+
+use crate::{
+    decode::{is_null, mismatch},
+    SqlDecode,
+};
+
+/// Parses the column text with [`FromStr`]. NULL is an error, as for any
+/// field that isn't an `Option`, rather than empty text.
+pub fn from_str<T: FromStr>(val: &ColumnData<'static>) -> Result<T>
+where
+    T::Err: Display,
+{
+    if is_null(val) {
+        return Err(mismatch::<T>(val));
+    }
+    let s = String::decode(val)?;
+    s.parse()
+        .map_err(|e| anyhow!("cannot parse `{}` as `{}`: {}", s, type_name::<T>(), e))
+}
+
+/// Decodes the column as `I`, then converts it with [`TryFrom`]. The value
+/// is decoded again to show it in an error, so a conversion that succeeds
+/// doesn't pay for it.
+pub fn try_from<I: SqlDecode + Debug, T: TryFrom<I>>(val: &ColumnData<'static>) -> Result<T>
+where
+    T::Error: Display,
+{
+    T::try_from(I::decode(val)?).map_err(|e| {
+        let v = I::decode(val).map_or_else(|_| String::new(), |v| format!("{:?}", v));
+        anyhow!("cannot convert {} to `{}`: {}", v, type_name::<T>(), e)
+    })
+}
+
+/// Applies `convert` to a column of an `Option` field, whose NULL is `None`.
+pub fn nullable<T>(
+    val: &ColumnData<'static>,
+    convert: impl FnOnce(&ColumnData<'static>) -> Result<T>,
+) -> Result<Option<T>> {
+    if is_null(val) {
+        Ok(None)
+    } else {
+        convert(val).map(Some)
+    }
+}
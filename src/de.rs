@@ -0,0 +1,334 @@
+//! A serde [`Deserializer`] over the cells of a row, for types that can't
+//! derive [`Row_Ext`](crate::Row_Ext).
+
+use std::fmt::Display;
+
+use anyhow::Result;
+use serde::de::value::{BorrowedStrDeserializer, Error, SeqDeserializer};
+use serde::de::{self, DeserializeOwned, DeserializeSeed, IntoDeserializer, Visitor};
+use serde::forward_to_deserialize_any;
+use tiberius::{Column, ColumnData, Row};
+
+use crate::decode::is_null;
+
+/// Deserializes a row into any `DeserializeOwned` type: a struct or map
+/// reads the columns by name, a tuple or sequence reads them in order, and a
+/// scalar reads the only column of the row. serde attributes such as
+/// `rename`, `default`, `alias` and `flatten` apply as usual.
+///
+/// ```ignore
+/// let totals: HashMap<String, Option<f64>> = tiberius_row::from_row(&row)?;
+/// let (id, name): (i32, String) = tiberius_row::from_row(&row)?;
+/// ```
+pub fn from_row<T: DeserializeOwned>(row: &Row) -> Result<T> {
+    from_cells(row.cells())
+}
+
+/// Like [`from_row`], from the `(Column, ColumnData)` pairs of a row.
+pub fn from_cells<'r, T: DeserializeOwned>(
+    cells: impl IntoIterator<Item = (&'r Column, &'r ColumnData<'static>)>,
+) -> Result<T> {
+    let cells = cells.into_iter().collect::<Vec<_>>();
+    Ok(T::deserialize(RowDeserializer::new(&cells))?)
+}
+
+/// The [`Deserializer`](de::Deserializer) behind [`from_row`]. Strings and
+/// binary values are lent to the visitor, so types deserialized through it
+/// directly can borrow `&str` and `&[u8]` fields from the row.
+pub struct RowDeserializer<'de> {
+    cells: &'de [(&'de Column, &'de ColumnData<'static>)],
+}
+
+impl<'de> RowDeserializer<'de> {
+    pub fn new(cells: &'de [(&'de Column, &'de ColumnData<'static>)]) -> Self {
+        RowDeserializer { cells }
+    }
+
+    /// The value of a row read as a scalar.
+    fn single(&self) -> Result<ValueDeserializer<'de>, Error> {
+        match self.cells {
+            [(col, val)] => Ok(ValueDeserializer {
+                column: col.name(),
+                val,
+            }),
+            _ => Err(de::Error::custom(format_args!(
+                "expected a single column, found {}",
+                self.cells.len()
+            ))),
+        }
+    }
+}
+
+macro_rules! forward_to_single {
+    ($($method:ident)*) => {
+        $(
+            fn $method<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+                self.single()?.$method(visitor)
+            }
+        )*
+    };
+}
+
+impl<'de> de::Deserializer<'de> for RowDeserializer<'de> {
+    type Error = Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        self.deserialize_map(visitor)
+    }
+
+    fn deserialize_map<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        visitor.visit_map(Columns {
+            cells: self.cells.iter(),
+            value: None,
+        })
+    }
+
+    fn deserialize_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        self.deserialize_map(visitor)
+    }
+
+    fn deserialize_seq<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        visitor.visit_seq(Values {
+            cells: self.cells.iter(),
+        })
+    }
+
+    fn deserialize_tuple<V: Visitor<'de>>(
+        self,
+        _len: usize,
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        self.deserialize_seq(visitor)
+    }
+
+    fn deserialize_tuple_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _len: usize,
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        self.deserialize_seq(visitor)
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_unit<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        visitor.visit_unit()
+    }
+
+    fn deserialize_unit_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        visitor.visit_unit()
+    }
+
+    fn deserialize_ignored_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        visitor.visit_unit()
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        name: &'static str,
+        variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        self.single()?.deserialize_enum(name, variants, visitor)
+    }
+
+    forward_to_single! {
+        deserialize_bool deserialize_i8 deserialize_i16 deserialize_i32 deserialize_i64
+        deserialize_i128 deserialize_u8 deserialize_u16 deserialize_u32 deserialize_u64
+        deserialize_u128 deserialize_f32 deserialize_f64 deserialize_char deserialize_str
+        deserialize_string deserialize_bytes deserialize_byte_buf deserialize_option
+        deserialize_identifier
+    }
+}
+
+/// The columns of a row as a map keyed by column name.
+struct Columns<'de> {
+    cells: std::slice::Iter<'de, (&'de Column, &'de ColumnData<'static>)>,
+    value: Option<ValueDeserializer<'de>>,
+}
+
+impl<'de> de::MapAccess<'de> for Columns<'de> {
+    type Error = Error;
+
+    fn next_key_seed<K: DeserializeSeed<'de>>(
+        &mut self,
+        seed: K,
+    ) -> Result<Option<K::Value>, Error> {
+        let Some((col, val)) = self.cells.next() else {
+            return Ok(None);
+        };
+        self.value = Some(ValueDeserializer {
+            column: col.name(),
+            val,
+        });
+        seed.deserialize(BorrowedStrDeserializer::new(col.name()))
+            .map(Some)
+    }
+
+    fn next_value_seed<V: DeserializeSeed<'de>>(&mut self, seed: V) -> Result<V::Value, Error> {
+        let value = self
+            .value
+            .take()
+            .ok_or_else(|| de::Error::custom("value requested before key"))?;
+        value.deserialize_seed(seed)
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.cells.len())
+    }
+}
+
+/// The values of a row in column order.
+struct Values<'de> {
+    cells: std::slice::Iter<'de, (&'de Column, &'de ColumnData<'static>)>,
+}
+
+impl<'de> de::SeqAccess<'de> for Values<'de> {
+    type Error = Error;
+
+    fn next_element_seed<T: DeserializeSeed<'de>>(
+        &mut self,
+        seed: T,
+    ) -> Result<Option<T::Value>, Error> {
+        let Some((col, val)) = self.cells.next() else {
+            return Ok(None);
+        };
+        ValueDeserializer {
+            column: col.name(),
+            val,
+        }
+        .deserialize_seed(seed)
+        .map(Some)
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.cells.len())
+    }
+}
+
+/// The value of one column. NULL is `none` to an `Option` and unit
+/// otherwise. Temporal values, GUIDs and decimals are visited as the text
+/// `#[row_ext(serde)]` renders them as.
+struct ValueDeserializer<'de> {
+    column: &'de str,
+    val: &'de ColumnData<'static>,
+}
+
+impl<'de> ValueDeserializer<'de> {
+    /// Deserializes the value, naming the column in errors.
+    fn deserialize_seed<T: DeserializeSeed<'de>>(self, seed: T) -> Result<T::Value, Error> {
+        let column = self.column;
+        seed.deserialize(self).map_err(|e| context(column, e))
+    }
+}
+
+fn context(column: &str, e: impl Display) -> Error {
+    de::Error::custom(format_args!("failed to decode column `{}`: {}", column, e))
+}
+
+impl<'de> de::Deserializer<'de> for ValueDeserializer<'de> {
+    type Error = Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        if is_null(self.val) {
+            return visitor.visit_unit();
+        }
+        match self.val {
+            ColumnData::U8(Some(v)) => visitor.visit_u8(*v),
+            ColumnData::I16(Some(v)) => visitor.visit_i16(*v),
+            ColumnData::I32(Some(v)) => visitor.visit_i32(*v),
+            ColumnData::I64(Some(v)) => visitor.visit_i64(*v),
+            ColumnData::F32(Some(v)) => visitor.visit_f32(*v),
+            ColumnData::F64(Some(v)) => visitor.visit_f64(*v),
+            ColumnData::Bit(Some(v)) => visitor.visit_bool(*v),
+            ColumnData::String(Some(v)) => visitor.visit_borrowed_str(v),
+            ColumnData::Binary(Some(v)) => {
+                visitor.visit_seq(SeqDeserializer::new(v.iter().copied()))
+            }
+            ColumnData::Guid(Some(v)) => visitor.visit_string(v.to_string()),
+            ColumnData::Numeric(Some(v)) => visitor.visit_string(v.to_string()),
+            ColumnData::Xml(Some(v)) => visitor.visit_string(v.to_string()),
+            _ => match crate::json::temporal(self.val) {
+                Some(v) => visitor.visit_string(v),
+                None => visitor.visit_unit(),
+            },
+        }
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        if is_null(self.val) {
+            visitor.visit_none()
+        } else {
+            visitor.visit_some(self)
+        }
+    }
+
+    /// Decimals are numbers to a float field.
+    fn deserialize_f64<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        match self.val {
+            ColumnData::Numeric(Some(v)) => {
+                visitor.visit_f64(v.to_string().parse().map_err(de::Error::custom)?)
+            }
+            _ => self.deserialize_any(visitor),
+        }
+    }
+
+    fn deserialize_f32<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        self.deserialize_f64(visitor)
+    }
+
+    /// Binary values are sequences of bytes, as serde represents `Vec<u8>`,
+    /// except to types asking for bytes such as `serde_bytes::ByteBuf`.
+    fn deserialize_bytes<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        match self.val {
+            ColumnData::Binary(Some(v)) => visitor.visit_borrowed_bytes(v),
+            _ => self.deserialize_any(visitor),
+        }
+    }
+
+    fn deserialize_byte_buf<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        self.deserialize_bytes(visitor)
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    /// Unit variants are read from their name.
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        match self.val {
+            ColumnData::String(Some(v)) => visitor.visit_enum(v.as_ref().into_deserializer()),
+            _ => self.deserialize_any(visitor),
+        }
+    }
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 char str string seq
+        unit unit_struct tuple tuple_struct map struct identifier ignored_any
+    }
+}
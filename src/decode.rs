@@ -0,0 +1,305 @@
+use std::time::Duration;
+
+use anyhow::{anyhow, Result};
+use serde_json::Value;
+use tiberius::{
+    numeric::{Decimal, Numeric},
+    ColumnData, ColumnType, FromSql, Uuid,
+};
+
+use crate::json;
+
+/// Decodes a single column value into a Rust type.
+///
+/// Every field of a struct deriving [`Row_Ext`](crate::Row_Ext) must
+/// implement this trait, unless it is marked with `#[row_ext(serde)]`.
+pub trait SqlDecode: Sized {
+    /// Decodes the value of a column that is present in the row.
+    fn decode(val: &ColumnData<'static>) -> Result<Self>;
+
+    /// The value to use when the column is not part of the row at all.
+    ///
+    /// Returns `None` by default, which makes a missing column an error.
+    fn missing() -> Option<Self> {
+        None
+    }
+
+    /// Whether columns of this type can be decoded, checked once per result
+    /// set by [`RowMapper`](crate::RowMapper).
+    ///
+    /// Returns `true` by default, leaving mismatches to [`decode`](Self::decode).
+    fn accepts(ty: ColumnType) -> bool {
+        let _ = ty;
+        true
+    }
+}
+
+fn is_int(ty: ColumnType) -> bool {
+    matches!(
+        ty,
+        ColumnType::Int1
+            | ColumnType::Int2
+            | ColumnType::Int4
+            | ColumnType::Int8
+            | ColumnType::Intn
+    )
+}
+
+fn is_float(ty: ColumnType) -> bool {
+    matches!(
+        ty,
+        ColumnType::Float4
+            | ColumnType::Float8
+            | ColumnType::Floatn
+            | ColumnType::Money
+            | ColumnType::Money4
+    )
+}
+
+fn is_binary(ty: ColumnType) -> bool {
+    matches!(
+        ty,
+        ColumnType::BigVarBin | ColumnType::BigBinary | ColumnType::Image
+    )
+}
+
+pub(crate) fn is_null(val: &ColumnData<'_>) -> bool {
+    match val {
+        ColumnData::U8(v) => v.is_none(),
+        ColumnData::I16(v) => v.is_none(),
+        ColumnData::I32(v) => v.is_none(),
+        ColumnData::I64(v) => v.is_none(),
+        ColumnData::F32(v) => v.is_none(),
+        ColumnData::F64(v) => v.is_none(),
+        ColumnData::Bit(v) => v.is_none(),
+        ColumnData::String(v) => v.is_none(),
+        ColumnData::Guid(v) => v.is_none(),
+        ColumnData::Binary(v) => v.is_none(),
+        ColumnData::Numeric(v) => v.is_none(),
+        ColumnData::Xml(v) => v.is_none(),
+        ColumnData::DateTime(v) => v.is_none(),
+        ColumnData::SmallDateTime(v) => v.is_none(),
+        ColumnData::Time(v) => v.is_none(),
+        ColumnData::Date(v) => v.is_none(),
+        ColumnData::DateTime2(v) => v.is_none(),
+        ColumnData::DateTimeOffset(v) => v.is_none(),
+    }
+}
+
+pub(crate) fn column_type(val: &ColumnData<'_>) -> &'static str {
+    match val {
+        ColumnData::U8(_) => "tinyint",
+        ColumnData::I16(_) => "smallint",
+        ColumnData::I32(_) => "int",
+        ColumnData::I64(_) => "bigint",
+        ColumnData::F32(_) => "real",
+        ColumnData::F64(_) => "float",
+        ColumnData::Bit(_) => "bit",
+        ColumnData::String(_) => "nvarchar",
+        ColumnData::Guid(_) => "uniqueidentifier",
+        ColumnData::Binary(_) => "varbinary",
+        ColumnData::Numeric(_) => "numeric",
+        ColumnData::Xml(_) => "xml",
+        ColumnData::DateTime(_) => "datetime",
+        ColumnData::SmallDateTime(_) => "smalldatetime",
+        ColumnData::Time(_) => "time",
+        ColumnData::Date(_) => "date",
+        ColumnData::DateTime2(_) => "datetime2",
+        ColumnData::DateTimeOffset(_) => "datetimeoffset",
+    }
+}
+
+/// The error for a value that `T` cannot be decoded from.
+pub(crate) fn mismatch<T>(val: &ColumnData<'_>) -> anyhow::Error {
+    if is_null(val) {
+        anyhow!("unexpected NULL for `{}`", std::any::type_name::<T>())
+    } else {
+        anyhow!(
+            "cannot decode {} as `{}`",
+            column_type(val),
+            std::any::type_name::<T>()
+        )
+    }
+}
+
+/// Decodes through tiberius' own [`FromSql`] conversion.
+pub(crate) fn from_sql<'a, T: FromSql<'a>>(val: &'a ColumnData<'static>) -> Result<T> {
+    match T::from_sql(val) {
+        Ok(Some(v)) => Ok(v),
+        _ => Err(mismatch::<T>(val)),
+    }
+}
+
+/// Nanoseconds since midnight of a `time` column, which has 100ns precision
+/// at its default scale of 7.
+pub(crate) fn time_nanos(val: &ColumnData<'_>) -> Option<u64> {
+    match val {
+        ColumnData::Time(Some(t)) => Some(t.increments() * 10u64.pow(9 - t.scale() as u32)),
+        _ => None,
+    }
+}
+
+impl<T: SqlDecode> SqlDecode for Option<T> {
+    fn decode(val: &ColumnData<'static>) -> Result<Self> {
+        if is_null(val) {
+            Ok(None)
+        } else {
+            T::decode(val).map(Some)
+        }
+    }
+
+    fn missing() -> Option<Self> {
+        Some(None)
+    }
+
+    fn accepts(ty: ColumnType) -> bool {
+        T::accepts(ty)
+    }
+}
+
+macro_rules! decode_int {
+    ($($ty:ty),*) => {
+        $(
+            impl SqlDecode for $ty {
+                fn decode(val: &ColumnData<'static>) -> Result<Self> {
+                    let v = match *val {
+                        ColumnData::U8(Some(v)) => v as i64,
+                        ColumnData::I16(Some(v)) => v as i64,
+                        ColumnData::I32(Some(v)) => v as i64,
+                        ColumnData::I64(Some(v)) => v,
+                        _ => return Err(mismatch::<Self>(val)),
+                    };
+                    <$ty>::try_from(v)
+                        .map_err(|_| anyhow!("{} is out of range for `{}`", v, stringify!($ty)))
+                }
+
+                fn accepts(ty: ColumnType) -> bool {
+                    is_int(ty)
+                }
+            }
+        )*
+    };
+}
+
+decode_int!(i8, i16, i32, i64, i128, u8, u16, u32, u64, u128);
+
+impl SqlDecode for f64 {
+    fn decode(val: &ColumnData<'static>) -> Result<Self> {
+        match *val {
+            ColumnData::F32(Some(v)) => Ok(v as f64),
+            ColumnData::F64(Some(v)) => Ok(v),
+            ColumnData::U8(Some(v)) => Ok(v as f64),
+            ColumnData::I16(Some(v)) => Ok(v as f64),
+            ColumnData::I32(Some(v)) => Ok(v as f64),
+            ColumnData::I64(Some(v)) => Ok(v as f64),
+            _ => Err(mismatch::<Self>(val)),
+        }
+    }
+
+    fn accepts(ty: ColumnType) -> bool {
+        is_float(ty) || is_int(ty)
+    }
+}
+
+impl SqlDecode for f32 {
+    fn decode(val: &ColumnData<'static>) -> Result<Self> {
+        match val {
+            ColumnData::F32(Some(v)) => Ok(*v),
+            _ => f64::decode(val).map(|v| v as f32),
+        }
+    }
+
+    fn accepts(ty: ColumnType) -> bool {
+        f64::accepts(ty)
+    }
+}
+
+impl SqlDecode for bool {
+    fn decode(val: &ColumnData<'static>) -> Result<Self> {
+        match *val {
+            ColumnData::Bit(Some(v)) => Ok(v),
+            _ => Err(mismatch::<Self>(val)),
+        }
+    }
+
+    fn accepts(ty: ColumnType) -> bool {
+        matches!(ty, ColumnType::Bit | ColumnType::Bitn)
+    }
+}
+
+/// Text columns decode as-is and a NULL text column becomes an empty string.
+/// Other columns use their textual form, with binary data base64 encoded.
+impl SqlDecode for String {
+    fn decode(val: &ColumnData<'static>) -> Result<Self> {
+        match val {
+            ColumnData::String(v) => Ok(v.as_deref().unwrap_or_default().to_string()),
+            _ => match json::to_json(val) {
+                Value::String(v) => Ok(v),
+                _ => Err(mismatch::<Self>(val)),
+            },
+        }
+    }
+
+    fn accepts(ty: ColumnType) -> bool {
+        !(is_int(ty) || is_float(ty) || matches!(ty, ColumnType::Bit | ColumnType::Bitn))
+    }
+}
+
+/// An owned column value as it comes from tiberius, for keeping columns
+/// whose type isn't known in advance.
+pub type SqlValue = ColumnData<'static>;
+
+impl SqlDecode for SqlValue {
+    fn decode(val: &ColumnData<'static>) -> Result<Self> {
+        Ok(val.clone())
+    }
+}
+
+/// Uses the same JSON form as `#[row_ext(serde)]`.
+impl SqlDecode for Value {
+    fn decode(val: &ColumnData<'static>) -> Result<Self> {
+        Ok(json::to_json(val))
+    }
+}
+
+impl SqlDecode for Vec<u8> {
+    fn decode(val: &ColumnData<'static>) -> Result<Self> {
+        match val {
+            ColumnData::Binary(Some(v)) => Ok(v.to_vec()),
+            _ => Err(mismatch::<Self>(val)),
+        }
+    }
+
+    fn accepts(ty: ColumnType) -> bool {
+        is_binary(ty)
+    }
+}
+
+/// Decodes `time` columns as the time elapsed since midnight.
+impl SqlDecode for Duration {
+    fn decode(val: &ColumnData<'static>) -> Result<Self> {
+        time_nanos(val)
+            .map(Duration::from_nanos)
+            .ok_or_else(|| mismatch::<Self>(val))
+    }
+}
+
+macro_rules! decode_from_sql {
+    ($($ty:ty),*) => {
+        $(
+            impl $crate::SqlDecode for $ty {
+                fn decode(val: &tiberius::ColumnData<'static>) -> anyhow::Result<Self> {
+                    $crate::decode::from_sql(val)
+                }
+            }
+        )*
+    };
+}
+
+decode_from_sql!(Decimal, Numeric, Uuid);
+
+mod chrono;
+#[cfg(feature = "jiff")]
+mod jiff;
+#[cfg(feature = "time")]
+mod time;
@@ -0,0 +1,84 @@
+//! Decoding into [`chrono`] types.
+//!
+//! `datetime`, `smalldatetime` and `datetime2` columns carry no offset and
+//! decode into `NaiveDateTime`, or into `DateTime<Utc>` read as UTC.
+//! `datetimeoffset` columns are stored as UTC plus an offset and decode into
+//! `DateTime<FixedOffset>` keeping that offset, or into `DateTime<Utc>`.
+//!
+//! With the `chrono-tz` feature, zone-less columns are read in the time zone
+//! set with `#[row_ext(tz)]` or [`set_default_tz`](crate::set_default_tz)
+//! instead, which also lets them decode into `DateTime<FixedOffset>` and
+//! `DateTime<chrono_tz::Tz>`.
+
+use anyhow::Result;
+use chrono::{DateTime, FixedOffset, NaiveDate, NaiveDateTime, NaiveTime, TimeDelta, Utc};
+use tiberius::ColumnData;
+
+use super::{from_sql, mismatch, time_nanos, SqlDecode};
+
+decode_from_sql!(NaiveDate, NaiveTime, NaiveDateTime);
+
+/// Decodes `time` columns as the time elapsed since midnight.
+impl SqlDecode for TimeDelta {
+    fn decode(val: &ColumnData<'static>) -> Result<Self> {
+        time_nanos(val)
+            .map(|ns| TimeDelta::nanoseconds(ns as i64))
+            .ok_or_else(|| mismatch::<Self>(val))
+    }
+}
+
+/// Decodes a zone-less column, read in the time zone in effect.
+#[cfg(feature = "chrono-tz")]
+fn zoned(val: &ColumnData<'static>) -> Result<Option<DateTime<FixedOffset>>> {
+    let naive: NaiveDateTime = from_sql(val)?;
+    match crate::tz::current_tz() {
+        Some(tz) => Ok(Some(crate::tz::localize(naive, tz)?.fixed_offset())),
+        None => Ok(None),
+    }
+}
+
+#[cfg(not(feature = "chrono-tz"))]
+fn zoned(_: &ColumnData<'static>) -> Result<Option<DateTime<FixedOffset>>> {
+    Ok(None)
+}
+
+impl SqlDecode for DateTime<FixedOffset> {
+    fn decode(val: &ColumnData<'static>) -> Result<Self> {
+        match val {
+            ColumnData::DateTimeOffset(Some(_)) => from_sql(val),
+            ColumnData::DateTime(Some(_))
+            | ColumnData::SmallDateTime(Some(_))
+            | ColumnData::DateTime2(Some(_)) => zoned(val)?.ok_or_else(|| mismatch::<Self>(val)),
+            _ => Err(mismatch::<Self>(val)),
+        }
+    }
+}
+
+impl SqlDecode for DateTime<Utc> {
+    fn decode(val: &ColumnData<'static>) -> Result<Self> {
+        match val {
+            ColumnData::DateTimeOffset(Some(_)) => {
+                Ok(from_sql::<DateTime<FixedOffset>>(val)?.to_utc())
+            }
+            ColumnData::DateTime(Some(_))
+            | ColumnData::SmallDateTime(Some(_))
+            | ColumnData::DateTime2(Some(_)) => match zoned(val)? {
+                Some(dt) => Ok(dt.to_utc()),
+                None => Ok(from_sql::<NaiveDateTime>(val)?.and_utc()),
+            },
+            _ => Err(mismatch::<Self>(val)),
+        }
+    }
+}
+
+/// Needs a time zone from `#[row_ext(tz)]` or the default. `datetimeoffset`
+/// columns are converted into it.
+#[cfg(feature = "chrono-tz")]
+impl SqlDecode for DateTime<chrono_tz::Tz> {
+    fn decode(val: &ColumnData<'static>) -> Result<Self> {
+        let Some(tz) = crate::tz::current_tz() else {
+            anyhow::bail!("no time zone set for `{}`", std::any::type_name::<Self>());
+        };
+        Ok(DateTime::<FixedOffset>::decode(val)?.with_timezone(&tz))
+    }
+}
@@ -0,0 +1,34 @@
+//! Decoding into [`jiff`] types, enabled by the `jiff` feature.
+
+use anyhow::Result;
+use jiff::{civil, Timestamp};
+use tiberius::{
+    time::time::{OffsetDateTime, PrimitiveDateTime},
+    ColumnData,
+};
+
+use super::{from_sql, SqlDecode};
+
+/// Decodes `datetime`, `smalldatetime` and `datetime2` columns.
+impl SqlDecode for civil::DateTime {
+    fn decode(val: &ColumnData<'static>) -> Result<Self> {
+        let v: PrimitiveDateTime = from_sql(val)?;
+        Ok(civil::DateTime::new(
+            v.year() as i16,
+            v.month() as i8,
+            v.day() as i8,
+            v.hour() as i8,
+            v.minute() as i8,
+            v.second() as i8,
+            v.nanosecond() as i32,
+        )?)
+    }
+}
+
+/// Decodes `datetimeoffset` columns.
+impl SqlDecode for Timestamp {
+    fn decode(val: &ColumnData<'static>) -> Result<Self> {
+        let v: OffsetDateTime = from_sql(val)?;
+        Ok(Timestamp::new(v.unix_timestamp(), v.nanosecond() as i32)?)
+    }
+}
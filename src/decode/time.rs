@@ -0,0 +1,18 @@
+//! Decoding into [`time`](::time) types, enabled by the `time` feature.
+
+use ::time::{Date, Duration, OffsetDateTime, PrimitiveDateTime, Time};
+use anyhow::Result;
+use tiberius::ColumnData;
+
+use super::{mismatch, time_nanos, SqlDecode};
+
+decode_from_sql!(Date, Time, PrimitiveDateTime, OffsetDateTime);
+
+/// Decodes `time` columns as the time elapsed since midnight.
+impl SqlDecode for Duration {
+    fn decode(val: &ColumnData<'static>) -> Result<Self> {
+        time_nanos(val)
+            .map(|ns| Duration::nanoseconds(ns as i64))
+            .ok_or_else(|| mismatch::<Self>(val))
+    }
+}
@@ -0,0 +1,45 @@
+//! Decoding bytes in legacy code pages, for `#[row_ext(encoding = "...")]`.
+//!
+//! Only binary columns are decoded. SQL Server has already converted the
+//! bytes of a VARCHAR column through its collation's code page by the time
+//! they arrive, so one stored with the wrong collation must be read as
+//! `CAST(col AS VARBINARY(MAX))` to get the original bytes back.
+
+use anyhow::{anyhow, bail, Result};
+
+/// The code page a binary column is decoded from, by its WHATWG label such
+/// as `gbk`, `big5` or `shift_jis`.
+#[derive(Debug, Clone, Copy)]
+pub struct Encoding<'a> {
+    pub label: &'a str,
+    /// Replace invalid sequences with U+FFFD instead of failing.
+    pub lossy: bool,
+}
+
+impl Encoding<'_> {
+    /// Decodes `bytes`. The derive has checked the label, which is looked
+    /// up again here.
+    pub(crate) fn decode(&self, bytes: &[u8]) -> Result<String> {
+        let encoding = encoding_rs::Encoding::for_label(self.label.as_bytes())
+            .ok_or_else(|| anyhow!("unknown encoding `{}`", self.label))?;
+        if self.lossy {
+            return Ok(encoding.decode_without_bom_handling(bytes).0.into_owned());
+        }
+        encoding
+            .decode_without_bom_handling_and_without_replacement(bytes)
+            .map(|text| text.into_owned())
+            .ok_or_else(|| anyhow!("invalid {} byte sequence", encoding.name()))
+    }
+
+    /// Encodes `text` for `ToParams`. Characters the code page lacks are an
+    /// error, or written as HTML character references with `lossy`.
+    pub(crate) fn encode(&self, text: &str) -> Result<Vec<u8>> {
+        let encoding = encoding_rs::Encoding::for_label(self.label.as_bytes())
+            .ok_or_else(|| anyhow!("unknown encoding `{}`", self.label))?;
+        let (bytes, _, unmappable) = encoding.encode(text);
+        if unmappable && !self.lossy {
+            bail!("`{}` cannot be encoded in {}", text, encoding.name());
+        }
+        Ok(bytes.into_owned())
+    }
+}
@@ -0,0 +1,58 @@
+//! Collecting the columns no other field reads, for `#[row_ext(extra)]`.
+
+use std::collections::{BTreeMap, HashMap};
+
+use anyhow::{Context as _, Result};
+use serde_json::{Map, Value};
+use tiberius::ColumnData;
+
+use crate::SqlDecode;
+
+/// A map a `#[row_ext(extra)]` field can collect the remaining columns into,
+/// keyed by column name.
+///
+/// Implemented for `HashMap<String, V>` and `BTreeMap<String, V>` with any
+/// [`SqlDecode`] value, such as [`SqlValue`](crate::SqlValue) or
+/// `serde_json::Value`, and for `serde_json::Map`.
+pub trait ExtraColumns: Default {
+    /// Adds a column to the map.
+    fn insert(&mut self, name: &str, val: &ColumnData<'static>) -> Result<()>;
+}
+
+impl<V: SqlDecode> ExtraColumns for HashMap<String, V> {
+    fn insert(&mut self, name: &str, val: &ColumnData<'static>) -> Result<()> {
+        HashMap::insert(self, name.to_string(), V::decode(val)?);
+        Ok(())
+    }
+}
+
+impl<V: SqlDecode> ExtraColumns for BTreeMap<String, V> {
+    fn insert(&mut self, name: &str, val: &ColumnData<'static>) -> Result<()> {
+        BTreeMap::insert(self, name.to_string(), V::decode(val)?);
+        Ok(())
+    }
+}
+
+impl ExtraColumns for Map<String, Value> {
+    fn insert(&mut self, name: &str, val: &ColumnData<'static>) -> Result<()> {
+        Map::insert(self, name.to_string(), Value::decode(val)?);
+        Ok(())
+    }
+}
+
+/// Collects every column not in `used`. A column selected twice keeps its
+/// last value.
+pub(crate) fn collect<'r, T: ExtraColumns>(
+    cells: impl IntoIterator<Item = (&'r str, &'r ColumnData<'static>)>,
+    used: &[&str],
+) -> Result<T> {
+    let mut extra = T::default();
+    for (name, val) in cells {
+        if !used.contains(&name) {
+            extra
+                .insert(name, val)
+                .with_context(|| format!("failed to decode column `{}`", name))?;
+        }
+    }
+    Ok(extra)
+}
@@ -0,0 +1,128 @@
+//! A field that tells a column missing from the row apart from a NULL one.
+
+use anyhow::Result;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use tiberius::{ColumnData, ToSql};
+
+use crate::{decode::is_null, SqlDecode};
+
+/// A column value that is either absent from the row, NULL, or present.
+///
+/// `Option<T>` reads both a missing column and NULL as `None`. `Field<T>`
+/// keeps them apart, for PATCH-style updates where only the selected columns
+/// should be written.
+///
+/// With serde, `Null` is `null` and `Value` its value. `Absent` also
+/// serializes as `null` unless skipped with
+/// `#[serde(default, skip_serializing_if = "Field::is_absent")]`, which
+/// round-trips a missing key as `Absent`.
+///
+/// As a parameter, `Null` and `Absent` bind as NULL, but
+/// [`ToParams`](crate::ToParams) leaves `Absent` fields out, so an
+/// `update_sql` only sets the columns that were read.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Field<T> {
+    #[default]
+    Absent,
+    Null,
+    Value(T),
+}
+
+impl<T> Field<T> {
+    pub fn is_absent(&self) -> bool {
+        matches!(self, Field::Absent)
+    }
+
+    pub fn is_null(&self) -> bool {
+        matches!(self, Field::Null)
+    }
+
+    pub fn is_value(&self) -> bool {
+        matches!(self, Field::Value(_))
+    }
+
+    /// The value, if present.
+    pub fn value(&self) -> Option<&T> {
+        match self {
+            Field::Value(v) => Some(v),
+            _ => None,
+        }
+    }
+
+    /// The value, if present, dropping the absent/NULL distinction.
+    pub fn into_value(self) -> Option<T> {
+        match self {
+            Field::Value(v) => Some(v),
+            _ => None,
+        }
+    }
+
+    pub fn as_ref(&self) -> Field<&T> {
+        match self {
+            Field::Absent => Field::Absent,
+            Field::Null => Field::Null,
+            Field::Value(v) => Field::Value(v),
+        }
+    }
+
+    pub fn map<U>(self, f: impl FnOnce(T) -> U) -> Field<U> {
+        match self {
+            Field::Absent => Field::Absent,
+            Field::Null => Field::Null,
+            Field::Value(v) => Field::Value(f(v)),
+        }
+    }
+}
+
+/// `None` is NULL, as a present column.
+impl<T> From<Option<T>> for Field<T> {
+    fn from(v: Option<T>) -> Self {
+        v.map_or(Field::Null, Field::Value)
+    }
+}
+
+impl<T: SqlDecode> SqlDecode for Field<T> {
+    fn decode(val: &ColumnData<'static>) -> Result<Self> {
+        if is_null(val) {
+            Ok(Field::Null)
+        } else {
+            T::decode(val).map(Field::Value)
+        }
+    }
+
+    fn missing() -> Option<Self> {
+        Some(Field::Absent)
+    }
+
+    fn accepts(ty: tiberius::ColumnType) -> bool {
+        T::accepts(ty)
+    }
+}
+
+/// `Absent` binds as NULL too. `ToParams` doesn't bind it at all.
+impl<T: ToSql> ToSql for Field<T>
+where
+    Option<T>: ToSql,
+{
+    fn to_sql(&self) -> ColumnData<'_> {
+        match self {
+            Field::Value(v) => v.to_sql(),
+            Field::Absent | Field::Null => crate::params::into_owned(None::<T>.to_sql()),
+        }
+    }
+}
+
+impl<T: Serialize> Serialize for Field<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            Field::Value(v) => serializer.serialize_some(v),
+            Field::Absent | Field::Null => serializer.serialize_none(),
+        }
+    }
+}
+
+impl<'de, T: Deserialize<'de>> Deserialize<'de> for Field<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Option::<T>::deserialize(deserializer).map(Field::from)
+    }
+}
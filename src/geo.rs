@@ -0,0 +1,159 @@
+//! Decoding spatial columns into geo-types, for `#[row_ext(wkt)]` and
+//! `#[row_ext(wkb)]`, enabled by the `geo` feature.
+//!
+//! SQL Server's `STAsText()` and `STAsBinary()` don't include the SRID. It is
+//! kept when the column holds EWKT (`SRID=4326;POINT (...)`) or EWKB.
+
+use anyhow::{anyhow, bail, Result};
+use geo_types::{
+    Geometry, GeometryCollection, LineString, MultiLineString, MultiPoint, MultiPolygon, Point,
+    Polygon,
+};
+use tiberius::ColumnData;
+use wkt::{ToWkt, TryFromWkt as _};
+
+use crate::decode::{column_type, is_null};
+
+mod wkb;
+
+/// The spatial formats a column can be read from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Wkt,
+    Wkb,
+}
+
+/// A geometry together with its spatial reference ID.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Spatial<G = Geometry<f64>> {
+    pub geometry: G,
+    /// `None` when the column didn't carry one.
+    pub srid: Option<i32>,
+}
+
+impl<G: ToWkt<f64>> Spatial<G> {
+    /// The geometry as WKT, the first parameter of
+    /// `geography::STGeomFromText(@P1, @P2)`. Bind the SRID, usually 4326
+    /// for `geography`, as the second.
+    pub fn to_wkt(&self) -> String {
+        self.geometry.wkt_string()
+    }
+}
+
+/// A type a `#[row_ext(wkt)]` or `#[row_ext(wkb)]` field can decode into.
+///
+/// Implemented for `geo_types::Geometry<f64>` and its variants, for
+/// [`Spatial`] to keep the SRID, and for `Option` to allow NULL.
+pub trait FromGeometry: Sized {
+    /// Converts a decoded geometry, `None` for NULL.
+    fn from_geometry(geometry: Option<Geometry<f64>>, srid: Option<i32>) -> Result<Self>;
+
+    /// The value to use when the column is not part of the row at all, like
+    /// [`SqlDecode::missing`](crate::SqlDecode::missing).
+    fn missing() -> Option<Self> {
+        None
+    }
+}
+
+impl FromGeometry for Geometry<f64> {
+    fn from_geometry(geometry: Option<Geometry<f64>>, _: Option<i32>) -> Result<Self> {
+        geometry.ok_or_else(|| anyhow!("unexpected NULL for `Geometry`"))
+    }
+}
+
+macro_rules! from_geometry {
+    ($($ty:ident),*) => {
+        $(
+            impl FromGeometry for $ty<f64> {
+                fn from_geometry(geometry: Option<Geometry<f64>>, _: Option<i32>) -> Result<Self> {
+                    match geometry {
+                        Some(Geometry::$ty(v)) => Ok(v),
+                        Some(geometry) => bail!(
+                            "cannot decode {} as `{}`",
+                            kind(&geometry),
+                            stringify!($ty)
+                        ),
+                        None => bail!("unexpected NULL for `{}`", stringify!($ty)),
+                    }
+                }
+            }
+        )*
+    };
+}
+
+from_geometry!(
+    Point,
+    LineString,
+    Polygon,
+    MultiPoint,
+    MultiLineString,
+    MultiPolygon,
+    GeometryCollection
+);
+
+impl<G: FromGeometry> FromGeometry for Spatial<G> {
+    fn from_geometry(geometry: Option<Geometry<f64>>, srid: Option<i32>) -> Result<Self> {
+        Ok(Spatial {
+            geometry: G::from_geometry(geometry, srid)?,
+            srid,
+        })
+    }
+}
+
+impl<G: FromGeometry> FromGeometry for Option<G> {
+    fn from_geometry(geometry: Option<Geometry<f64>>, srid: Option<i32>) -> Result<Self> {
+        geometry
+            .map(|geometry| G::from_geometry(Some(geometry), srid))
+            .transpose()
+    }
+
+    fn missing() -> Option<Self> {
+        Some(None)
+    }
+}
+
+/// Reads a WKT text or WKB binary column.
+pub(crate) fn decode<T: FromGeometry>(val: &ColumnData<'static>, format: Format) -> Result<T> {
+    let (geometry, srid) = match (format, val) {
+        _ if is_null(val) => (None, None),
+        (Format::Wkt, ColumnData::String(Some(text))) => {
+            let (geometry, srid) = parse_wkt(text)?;
+            (Some(geometry), srid)
+        }
+        (Format::Wkb, ColumnData::Binary(Some(bytes))) => {
+            let (geometry, srid) = wkb::parse(bytes)?;
+            (Some(geometry), srid)
+        }
+        (Format::Wkt, _) => bail!("cannot decode {} as WKT", column_type(val)),
+        (Format::Wkb, _) => bail!("cannot decode {} as WKB", column_type(val)),
+    };
+    T::from_geometry(geometry, srid)
+}
+
+/// The WKT name of the geometry's type.
+fn kind(geometry: &Geometry<f64>) -> &'static str {
+    match geometry {
+        Geometry::Point(_) => "POINT",
+        Geometry::Line(_) | Geometry::LineString(_) => "LINESTRING",
+        Geometry::Polygon(_) | Geometry::Rect(_) | Geometry::Triangle(_) => "POLYGON",
+        Geometry::MultiPoint(_) => "MULTIPOINT",
+        Geometry::MultiLineString(_) => "MULTILINESTRING",
+        Geometry::MultiPolygon(_) => "MULTIPOLYGON",
+        Geometry::GeometryCollection(_) => "GEOMETRYCOLLECTION",
+    }
+}
+
+fn parse_wkt(text: &str) -> Result<(Geometry<f64>, Option<i32>)> {
+    let (srid, text) = match text.split_once(';') {
+        Some((srid, rest)) if srid.trim_start().starts_with("SRID=") => {
+            let srid = srid.trim_start()["SRID=".len()..]
+                .parse()
+                .map_err(|_| anyhow!("invalid SRID in `{}`", text))?;
+            (Some(srid), rest)
+        }
+        _ => (None, text),
+    };
+    let geometry =
+        Geometry::try_from_wkt_str(text.trim()).map_err(|e| anyhow!("invalid WKT: {}", e))?;
+    Ok((geometry, srid))
+}
@@ -0,0 +1,120 @@
+//! A reader for 2D WKB, as returned by `STAsBinary()`, and EWKB with an SRID.
+
+use anyhow::{anyhow, bail, Result};
+use geo_types::{
+    Coord, Geometry, GeometryCollection, LineString, MultiLineString, MultiPoint, MultiPolygon,
+    Point, Polygon,
+};
+
+const SRID_FLAG: u32 = 0x2000_0000;
+
+/// Parses a WKB geometry and the SRID of an EWKB one.
+pub(super) fn parse(bytes: &[u8]) -> Result<(Geometry<f64>, Option<i32>)> {
+    let mut reader = Reader { bytes, pos: 0 };
+    let (geometry, srid) = reader.geometry()?;
+    if reader.pos != bytes.len() {
+        bail!("invalid WKB: trailing bytes after the geometry");
+    }
+    Ok((geometry, srid))
+}
+
+struct Reader<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl Reader<'_> {
+    fn take<const N: usize>(&mut self) -> Result<[u8; N]> {
+        let bytes = self
+            .bytes
+            .get(self.pos..self.pos + N)
+            .ok_or_else(|| anyhow!("invalid WKB: unexpected end of data"))?;
+        self.pos += N;
+        Ok(bytes.try_into().unwrap())
+    }
+
+    fn u32(&mut self, little: bool) -> Result<u32> {
+        let bytes = self.take()?;
+        Ok(if little {
+            u32::from_le_bytes(bytes)
+        } else {
+            u32::from_be_bytes(bytes)
+        })
+    }
+
+    fn f64(&mut self, little: bool) -> Result<f64> {
+        let bytes = self.take()?;
+        Ok(if little {
+            f64::from_le_bytes(bytes)
+        } else {
+            f64::from_be_bytes(bytes)
+        })
+    }
+
+    fn coord(&mut self, little: bool) -> Result<Coord<f64>> {
+        Ok(Coord {
+            x: self.f64(little)?,
+            y: self.f64(little)?,
+        })
+    }
+
+    fn line(&mut self, little: bool) -> Result<LineString<f64>> {
+        let n = self.u32(little)?;
+        (0..n).map(|_| self.coord(little)).collect()
+    }
+
+    fn polygon(&mut self, little: bool) -> Result<Polygon<f64>> {
+        let n = self.u32(little)?;
+        let mut rings = (0..n)
+            .map(|_| self.line(little))
+            .collect::<Result<Vec<_>>>()?;
+        if rings.is_empty() {
+            return Ok(Polygon::new(LineString::new(vec![]), vec![]));
+        }
+        let exterior = rings.remove(0);
+        Ok(Polygon::new(exterior, rings))
+    }
+
+    /// The geometries of a multi geometry or collection, each with its own
+    /// header.
+    fn parts<G: TryFrom<Geometry<f64>>>(&mut self, little: bool) -> Result<Vec<G>>
+    where
+        G::Error: std::error::Error + Send + Sync + 'static,
+    {
+        let n = self.u32(little)?;
+        (0..n)
+            .map(|_| Ok(G::try_from(self.geometry()?.0)?))
+            .collect()
+    }
+
+    fn geometry(&mut self) -> Result<(Geometry<f64>, Option<i32>)> {
+        let little = match self.take::<1>()?[0] {
+            0 => false,
+            1 => true,
+            b => bail!("invalid WKB: unknown byte order {}", b),
+        };
+        let kind = self.u32(little)?;
+        let srid = if kind & SRID_FLAG != 0 {
+            Some(self.u32(little)? as i32)
+        } else {
+            None
+        };
+        let geometry = match kind & !SRID_FLAG {
+            1 => {
+                let coord = self.coord(little)?;
+                if coord.x.is_nan() && coord.y.is_nan() {
+                    bail!("cannot decode an empty POINT");
+                }
+                Geometry::Point(Point(coord))
+            }
+            2 => Geometry::LineString(self.line(little)?),
+            3 => Geometry::Polygon(self.polygon(little)?),
+            4 => Geometry::MultiPoint(MultiPoint(self.parts(little)?)),
+            5 => Geometry::MultiLineString(MultiLineString(self.parts(little)?)),
+            6 => Geometry::MultiPolygon(MultiPolygon(self.parts(little)?)),
+            7 => Geometry::GeometryCollection(GeometryCollection(self.parts(little)?)),
+            kind => bail!("unsupported WKB geometry type {}", kind),
+        };
+        Ok((geometry, srid))
+    }
+}
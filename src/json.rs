@@ -0,0 +1,127 @@
+use anyhow::{anyhow, bail, Result};
+use base64::Engine as _;
+use chrono::{DateTime, FixedOffset, NaiveDate, NaiveDateTime, NaiveTime};
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+use tiberius::{ColumnData, FromSql as _};
+
+use crate::decode::{column_type, is_null};
+
+/// Converts a column value into JSON, the representation used by
+/// `#[row_ext(serde)]` fields.
+pub(crate) fn to_json(val: &ColumnData<'static>) -> Value {
+    match val {
+        ColumnData::I64(v) => v.map_or(Value::Null, |v| Value::Number(v.into())),
+        ColumnData::I32(v) => v.map_or(Value::Null, |v| Value::Number(v.into())),
+        ColumnData::I16(v) => v.map_or(Value::Null, |v| Value::Number(v.into())),
+        ColumnData::U8(v) => v.map_or(Value::Null, |v| Value::Number(v.into())),
+        ColumnData::F32(v) => v.map_or(Value::Null, |v| float(v as f64)),
+        ColumnData::F64(v) => v.map_or(Value::Null, float),
+        ColumnData::Bit(v) => v.map_or(Value::Null, Value::Bool),
+        ColumnData::String(v) => match v {
+            Some(v) => Value::String(v.to_string()),
+            None => Value::String(String::new()),
+        },
+        ColumnData::Numeric(v) => v.map_or(Value::Null, |v| Value::String(v.to_string())),
+        ColumnData::Guid(v) => v.map_or(Value::Null, |v| Value::String(v.to_string())),
+        ColumnData::Binary(v) => match v {
+            Some(v) => Value::String(base64::engine::general_purpose::STANDARD.encode(v)),
+            None => Value::Null,
+        },
+        ColumnData::Xml(v) => match v {
+            Some(v) => Value::String(v.to_string()),
+            None => Value::Null,
+        },
+        ColumnData::DateTime(_)
+        | ColumnData::SmallDateTime(_)
+        | ColumnData::DateTime2(_)
+        | ColumnData::Date(_)
+        | ColumnData::Time(_)
+        | ColumnData::DateTimeOffset(_) => temporal(val).map_or(Value::Null, Value::String),
+    }
+}
+
+/// Temporal values are rendered in ISO 8601, with RFC 3339 for
+/// `datetimeoffset`, so chrono's serde implementations can parse them back.
+/// `None` for NULL and for other column types.
+pub(crate) fn temporal(val: &ColumnData<'static>) -> Option<String> {
+    match val {
+        ColumnData::DateTime(_) | ColumnData::SmallDateTime(_) | ColumnData::DateTime2(_) => {
+            text(NaiveDateTime::from_sql(val), |v| {
+                v.format("%Y-%m-%dT%H:%M:%S%.f").to_string()
+            })
+        }
+        ColumnData::Date(_) => text(NaiveDate::from_sql(val), |v| v.to_string()),
+        ColumnData::Time(_) => text(NaiveTime::from_sql(val), |v| v.to_string()),
+        ColumnData::DateTimeOffset(_) => {
+            text(DateTime::<FixedOffset>::from_sql(val), |v| v.to_rfc3339())
+        }
+        _ => None,
+    }
+}
+
+/// Deserializes the JSON document stored in a text column, or the part of it
+/// at `path`, for `#[row_ext(json)]`. A missing column reads as `null`.
+pub(crate) fn decode<T: DeserializeOwned>(
+    val: Option<&ColumnData<'static>>,
+    path: Option<&str>,
+) -> Result<T> {
+    let value = match val {
+        Some(val) => parse(val)?,
+        None => Value::Null,
+    };
+    let value = match path {
+        Some(path) => select(value, path)?,
+        None => value,
+    };
+    Ok(serde_json::from_value(value)?)
+}
+
+fn parse(val: &ColumnData<'static>) -> Result<Value> {
+    match val {
+        ColumnData::String(Some(v)) => Ok(serde_json::from_str(v)?),
+        _ if is_null(val) => Ok(Value::Null),
+        _ => bail!("cannot decode {} as JSON", column_type(val)),
+    }
+}
+
+/// Takes the value at `path` out of `value`. `path` is a JSONPath subset made
+/// of `$` followed by `.key`, `['key']` and `[index]` steps, like
+/// `$.items[0].sku`. A path leading nowhere selects `null`.
+fn select(mut value: Value, path: &str) -> Result<Value> {
+    let invalid = || anyhow!("invalid json_path `{}`", path);
+    let mut rest = path.strip_prefix('$').ok_or_else(invalid)?;
+    while !rest.is_empty() {
+        if let Some(step) = rest.strip_prefix('.') {
+            let end = step.find(['.', '[']).unwrap_or(step.len());
+            if end == 0 {
+                return Err(invalid());
+            }
+            value = value.get_mut(&step[..end]).map_or(Value::Null, Value::take);
+            rest = &step[end..];
+        } else if let Some(step) = rest.strip_prefix('[') {
+            let end = step.find(']').ok_or_else(invalid)?;
+            let key = &step[..end];
+            value = match key.strip_prefix('\'').and_then(|k| k.strip_suffix('\'')) {
+                Some(key) => value.get_mut(key),
+                None => value.get_mut(key.parse::<usize>().map_err(|_| invalid())?),
+            }
+            .map_or(Value::Null, Value::take);
+            rest = &step[end + 1..];
+        } else {
+            return Err(invalid());
+        }
+    }
+    Ok(value)
+}
+
+fn text<T>(v: tiberius::Result<Option<T>>, format: impl FnOnce(T) -> String) -> Option<String> {
+    v.ok().flatten().map(format)
+}
+
+fn float(v: f64) -> Value {
+    match serde_json::Number::from_f64(v) {
+        Some(num) => Value::Number(num),
+        None => Value::String(v.to_string()),
+    }
+}
@@ -1,10 +1,82 @@
-use proc_macro::TokenStream;
-use rowext::process_row_ext;
+//! 将 Tiberius 的 [`Row`](tiberius::Row) 转换为 Rust 结构体。
+//!
+//! Derive [`Row_Ext`] on a struct with named fields to get a `from_row`
+//! constructor. Each field is looked up by column name and decoded with
+//! [`SqlDecode`].
+//!
+//! ```ignore
+//! use tiberius_row::Row_Ext;
+//!
+//! #[derive(Row_Ext)]
+//! struct User {
+//!     id: i32,
+//!     name: String,
+//!     email: Option<String>,
+//! }
+//!
+//! let user = User::from_row(row)?;
+//! ```
+//!
+//! `from_row_ref` decodes from a borrowed row instead, which also lets
+//! `&str` and `&[u8]` fields of a struct with a lifetime borrow from it.
+//!
+//! Types that can't derive [`Row_Ext`], such as `HashMap<String, T>`,
+//! tuples or structs from other crates, can be deserialized with serde
+//! through [`from_row`].
+//!
+//! On the write side, derive [`ToParams`](derive@ToParams) to bind a
+//! struct's fields as query parameters, and [`Procedure`](derive@Procedure)
+//! to call a stored procedure with OUTPUT parameters.
 
-mod rowext;
+#[doc(hidden)]
+pub mod __private;
+mod borrow;
+#[cfg(feature = "compress")]
+mod compress;
+mod convert;
+mod de;
+mod decode;
+#[cfg(feature = "encoding")]
+mod encoding;
+mod extra;
+mod field;
+#[cfg(feature = "geo")]
+mod geo;
+mod json;
+mod mapper;
+mod normalize;
+mod params;
+mod positional;
+mod procedure;
+mod query;
+mod results;
+mod split;
+mod stream;
+#[cfg(feature = "chrono-tz")]
+mod tz;
+mod update;
+#[cfg(feature = "xml")]
+mod xml;
 
-#[proc_macro_derive(Row_Ext)]
-pub fn derive_row_ext(input: TokenStream) -> TokenStream {
-    let input = syn::parse_macro_input!(input as syn::DeriveInput);
-    process_row_ext(input).into()
-}
+pub use borrow::SqlDecodeRef;
+#[cfg(feature = "compress")]
+pub use compress::{compress, compress_text};
+pub use de::{from_cells, from_row, RowDeserializer};
+pub use decode::{SqlDecode, SqlValue};
+pub use extra::ExtraColumns;
+pub use field::Field;
+#[cfg(feature = "geo")]
+pub use geo::{FromGeometry, Spatial};
+pub use mapper::{FromRow, FromRows, RowMapper};
+pub use params::{Params, ToParams};
+pub use procedure::{exec_procedure, ProcResponse, Procedure};
+pub use query::{query_all, query_as, query_one, query_optional, query_stream};
+pub use results::{from_results, query_results, FromResults};
+pub use split::SplitCollection;
+pub use stream::{Chunks, RowStream};
+pub use tiberius_row_derive::{Procedure, Row_Ext, ToParams};
+#[cfg(feature = "chrono-tz")]
+pub use tz::{default_tz, set_default_tz};
+pub use update::ChangedFields;
+#[cfg(feature = "xml")]
+pub use xml::XmlDocument;
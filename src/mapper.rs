@@ -0,0 +1,140 @@
+//! Decoding every row of a result set through column ordinals resolved once
+//! from its metadata.
+
+use std::marker::PhantomData;
+
+use anyhow::{bail, Result};
+use tiberius::{Column, ColumnData, ColumnType, Row};
+
+use crate::__private::{Cells, Field};
+
+/// A type decoded from a whole row: structs deriving
+/// [`Row_Ext`](crate::Row_Ext) that don't borrow from the row read their
+/// columns by name, tuples of [`SqlDecode`](crate::SqlDecode) types read the
+/// columns in order, and a single `SqlDecode` type reads the first column.
+pub trait FromRow: Sized {
+    /// The fields read from columns, in field order.
+    #[doc(hidden)]
+    const FIELDS: &'static [Field<'static>];
+
+    #[doc(hidden)]
+    fn decode_row(cells: &Cells<'_, '_>) -> Result<Self>;
+
+    /// The ordinal in `columns` of each entry of [`FIELDS`](Self::FIELDS),
+    /// checking that its type can be decoded.
+    #[doc(hidden)]
+    fn resolve(columns: &[Column]) -> Result<Vec<Option<usize>>> {
+        Self::FIELDS
+            .iter()
+            .map(|field| {
+                let i = columns.iter().position(|col| field.reads(col.name()));
+                if let Some(i) = i {
+                    check(&columns[i], field)?;
+                }
+                Ok(i)
+            })
+            .collect()
+    }
+
+    fn from_row_ref(row: &Row) -> Result<Self> {
+        Self::from_cells(row.cells())
+    }
+
+    fn from_cells<'r>(
+        cells: impl IntoIterator<Item = (&'r Column, &'r ColumnData<'static>)>,
+    ) -> Result<Self> {
+        let cells = cells.into_iter().collect::<Vec<_>>();
+        Self::decode_row(&Cells::new(&cells))
+    }
+}
+
+/// Checks that `column`'s type can be decoded into `field`. The type of
+/// columns built without one is not checked.
+pub(crate) fn check(column: &Column, field: &Field<'_>) -> Result<()> {
+    let ty = column.column_type();
+    if ty != ColumnType::Null && !(field.accepts)(ty) {
+        bail!(
+            "column `{}` of type {:?} cannot be decoded into field `{}`",
+            column.name(),
+            ty,
+            field.name
+        );
+    }
+    Ok(())
+}
+
+/// A type decoded from all the rows of a result set: `Vec<T>` for any
+/// number of rows and `Option<T>` for zero or one.
+pub trait FromRows: Sized {
+    fn from_rows(rows: Vec<Row>) -> Result<Self>;
+}
+
+impl<T: FromRow> FromRows for Vec<T> {
+    fn from_rows(rows: Vec<Row>) -> Result<Self> {
+        let Some(first) = rows.first() else {
+            return Ok(Vec::new());
+        };
+        let mapper = RowMapper::<T>::new(first.columns())?;
+        rows.iter().map(|row| mapper.decode(row)).collect()
+    }
+}
+
+impl<T: FromRow> FromRows for Option<T> {
+    fn from_rows(rows: Vec<Row>) -> Result<Self> {
+        match rows.as_slice() {
+            [] => Ok(None),
+            [row] => T::from_row_ref(row).map(Some),
+            _ => bail!("expected at most one row, found {}", rows.len()),
+        }
+    }
+}
+
+/// Decodes the rows of one result set into `T`.
+///
+/// The column of every field is looked up by name once, from the result
+/// set's metadata, and its type checked. Each row is then decoded by column
+/// ordinal.
+///
+/// ```ignore
+/// let mut stream = client.query("SELECT id, name FROM users", &[]).await?;
+/// let columns = stream.columns().await?.unwrap_or_default().to_vec();
+/// let mapper = RowMapper::<User>::new(&columns)?;
+/// for row in stream.into_first_result().await? {
+///     let user = mapper.decode(&row)?;
+/// }
+/// ```
+pub struct RowMapper<T> {
+    ordinals: Vec<Option<usize>>,
+    columns: usize,
+    _marker: PhantomData<fn() -> T>,
+}
+
+impl<T: FromRow> RowMapper<T> {
+    /// Resolves the columns of `T`'s fields in a result set's columns.
+    pub fn new(columns: &[Column]) -> Result<Self> {
+        Ok(RowMapper {
+            ordinals: T::resolve(columns)?,
+            columns: columns.len(),
+            _marker: PhantomData,
+        })
+    }
+
+    pub fn decode(&self, row: &Row) -> Result<T> {
+        self.decode_cells(row.cells())
+    }
+
+    pub fn decode_cells<'r>(
+        &self,
+        cells: impl IntoIterator<Item = (&'r Column, &'r ColumnData<'static>)>,
+    ) -> Result<T> {
+        let cells = cells.into_iter().collect::<Vec<_>>();
+        if cells.len() != self.columns {
+            bail!(
+                "row has {} columns, but the mapper was built for {}",
+                cells.len(),
+                self.columns
+            );
+        }
+        T::decode_row(&Cells::with_ordinals(&cells, &self.ordinals))
+    }
+}
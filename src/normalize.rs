@@ -0,0 +1,78 @@
+//! Clean-up of text columns before decoding, for `#[row_ext(trim)]`,
+//! `#[row_ext(empty_as_none)]` and `#[row_ext(bool_from)]`.
+
+use std::borrow::Cow;
+
+use anyhow::{bail, Result};
+use tiberius::ColumnData;
+
+use crate::__private::Field;
+
+/// Which ends of a text value `#[row_ext(trim)]` strips whitespace from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Trim {
+    Left,
+    Right,
+    Both,
+}
+
+impl Trim {
+    pub(crate) fn apply(self, s: &str) -> &str {
+        match self {
+            Trim::Left => s.trim_start(),
+            Trim::Right => s.trim_end(),
+            Trim::Both => s.trim(),
+        }
+    }
+}
+
+/// Applies the field's options to a column value. A binary column is first
+/// decompressed with `compressed` and decoded into text with `encoding`.
+/// Text is then trimmed, an empty string turned into NULL, and finally
+/// mapped onto a bit with `bool_from`. Other columns are left as they are.
+pub(crate) fn apply<'r>(
+    val: &'r ColumnData<'static>,
+    field: &Field<'_>,
+) -> Result<Cow<'r, ColumnData<'static>>> {
+    #[allow(unused_mut)]
+    let mut val = Cow::Borrowed(val);
+    #[cfg(feature = "compress")]
+    if let (Some(compressed), ColumnData::Binary(bytes)) = (field.compressed, &*val) {
+        val = Cow::Owned(crate::compress::decompress(bytes.as_deref(), compressed)?);
+    }
+    #[cfg(feature = "encoding")]
+    if let (Some(encoding), ColumnData::Binary(bytes)) = (&field.encoding, &*val) {
+        let text = bytes.as_deref().map(|b| encoding.decode(b)).transpose()?;
+        val = Cow::Owned(ColumnData::String(text.map(Into::into)));
+    }
+    text_options(val, field)
+}
+
+fn text_options<'r>(
+    val: Cow<'r, ColumnData<'static>>,
+    field: &Field<'_>,
+) -> Result<Cow<'r, ColumnData<'static>>> {
+    if field.trim.is_none() && !field.empty_as_none && field.bool_from.is_none() {
+        return Ok(val);
+    }
+    let ColumnData::String(text) = &*val else {
+        return Ok(val);
+    };
+    let mut text = text.as_deref();
+    if let Some(trim) = field.trim {
+        text = text.map(|s| trim.apply(s));
+    }
+    if field.empty_as_none {
+        text = text.filter(|s| !s.is_empty());
+    }
+    let val = match field.bool_from {
+        Some([yes, no]) => ColumnData::Bit(match text {
+            Some(s) if s == yes => Some(true),
+            Some(s) if s == no => Some(false),
+            Some(s) => bail!("`{}` is neither `{}` nor `{}`", s, yes, no),
+            None => None,
+        }),
+        None => ColumnData::String(text.map(|s| s.to_string().into())),
+    };
+    Ok(Cow::Owned(val))
+}
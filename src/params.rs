@@ -0,0 +1,247 @@
+//! Binding a struct's fields as query parameters, with
+//! [`ToParams`](crate::ToParams).
+
+use std::borrow::Cow;
+use std::fmt::Display;
+
+use anyhow::{bail, Result};
+use tiberius::{ColumnData, IntoSql, Query, ToSql};
+
+use crate::SqlValue;
+
+/// A type whose fields can be bound as query parameters. Derive it with
+/// [`ToParams`](derive@crate::ToParams) next to `Row_Ext` or `Serialize`.
+pub trait ToParams {
+    fn to_params(&self) -> Result<Params>;
+}
+
+/// Query parameters in field order, with the column each one is written to.
+///
+/// Values are converted with tiberius' [`ToSql`], so each keeps its SQL
+/// type: `Decimal` binds as NUMERIC, `NaiveDateTime` as DATETIME2, `Uuid` as
+/// UNIQUEIDENTIFIER and `None` as a NULL of the field's type.
+#[derive(Debug, Clone, Default)]
+pub struct Params {
+    columns: Vec<&'static str>,
+    values: Vec<Param>,
+}
+
+impl Params {
+    /// Adds a parameter for `column`.
+    pub fn push<T: ToSql + ?Sized>(&mut self, column: &'static str, value: &T) {
+        self.columns.push(column);
+        self.values.push(Param(into_owned(value.to_sql())));
+    }
+
+    pub fn columns(&self) -> &[&'static str] {
+        &self.columns
+    }
+
+    pub fn values(&self) -> impl Iterator<Item = &SqlValue> + '_ {
+        self.values.iter().map(|param| &param.0)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    /// The parameters for `Client::execute` and `Client::query`, as `@P1`,
+    /// `@P2` and so on.
+    pub fn as_refs(&self) -> Vec<&dyn ToSql> {
+        self.values
+            .iter()
+            .map(|param| param as &dyn ToSql)
+            .collect()
+    }
+
+    /// Binds the parameters to `query`, after any bound before.
+    pub fn bind(self, query: &mut Query<'_>) {
+        for param in self.values {
+            query.bind(param);
+        }
+    }
+
+    /// `INSERT INTO table ([a], [b]) VALUES (@P1, @P2)` for the columns.
+    pub fn insert_sql(&self, table: &str) -> String {
+        let columns = self.columns.iter().map(|c| quote(c)).collect::<Vec<_>>();
+        let values = (1..=self.len())
+            .map(|i| format!("@P{}", i))
+            .collect::<Vec<_>>();
+        format!(
+            "INSERT INTO {} ({}) VALUES ({})",
+            table,
+            columns.join(", "),
+            values.join(", ")
+        )
+    }
+
+    /// `UPDATE table SET [a] = @P1 WHERE [id] = @P2` for the columns, which
+    /// are matched on the `keys` columns instead of set. Fails when every
+    /// column is a key, leaving nothing to set.
+    pub fn update_sql(&self, table: &str, keys: &[&str]) -> Result<String> {
+        let assign = |(i, column): (usize, &&str)| format!("{} = @P{}", quote(column), i + 1);
+        let (keys, set): (Vec<_>, Vec<_>) = self
+            .columns
+            .iter()
+            .enumerate()
+            .partition(|(_, column)| keys.contains(column));
+        if set.is_empty() {
+            bail!("no columns to update in {}, all are keys", table);
+        }
+        let set = set.into_iter().map(assign).collect::<Vec<_>>();
+        let keys = keys.into_iter().map(assign).collect::<Vec<_>>();
+        let mut sql = format!("UPDATE {} SET {}", table, set.join(", "));
+        if !keys.is_empty() {
+            sql.push_str(" WHERE ");
+            sql.push_str(&keys.join(" AND "));
+        }
+        Ok(sql)
+    }
+}
+
+// The inverses of the `#[row_ext]` options that change a column's value
+// before it is decoded, so that `ToParams` writes what `Row_Ext` reads.
+
+/// A field value for the functions below: `T`, `Option<T>` or
+/// [`Field<T>`](crate::Field), with NULL as `None`.
+pub trait Nullable<T> {
+    fn get(&self) -> Option<&T>;
+}
+
+impl<T> Nullable<T> for T {
+    fn get(&self) -> Option<&T> {
+        Some(self)
+    }
+}
+
+impl<T> Nullable<T> for Option<T> {
+    fn get(&self) -> Option<&T> {
+        self.as_ref()
+    }
+}
+
+impl<T> Nullable<T> for crate::Field<T> {
+    fn get(&self) -> Option<&T> {
+        self.value()
+    }
+}
+
+/// The string `#[row_ext(bool_from = [yes, no])]` reads as the value.
+pub fn bool_text(
+    value: &impl Nullable<bool>,
+    [yes, no]: [&'static str; 2],
+) -> Option<&'static str> {
+    value.get().map(|&v| if v { yes } else { no })
+}
+
+/// The text of a `String` field, for `encoding` and `compressed`.
+pub fn text(value: &impl Nullable<String>) -> Option<&str> {
+    value.get().map(String::as_str)
+}
+
+/// The bytes of a `Vec<u8>` field, for `compressed`.
+pub fn bytes(value: &impl Nullable<Vec<u8>>) -> Option<&[u8]> {
+    value.get().map(Vec::as_slice)
+}
+
+/// The text `#[row_ext(from_str)]` parses the value from.
+pub fn display<T: Display>(value: &impl Nullable<T>) -> Option<String> {
+    value.get().map(ToString::to_string)
+}
+
+/// The elements of a `#[row_ext(split)]` collection joined with `sep`.
+pub fn join<C>(value: &impl Nullable<C>, sep: &str) -> Option<String>
+where
+    for<'a> &'a C: IntoIterator,
+    for<'a> <&'a C as IntoIterator>::Item: Display,
+{
+    value.get().map(|items| {
+        items
+            .into_iter()
+            .map(|item| item.to_string())
+            .collect::<Vec<_>>()
+            .join(sep)
+    })
+}
+
+/// Text encoded in the code page of `#[row_ext(encoding)]`.
+#[cfg(feature = "encoding")]
+pub fn encode(
+    text: Option<&str>,
+    encoding: crate::__private::Encoding<'_>,
+) -> Result<Option<Vec<u8>>> {
+    text.map(|text| encoding.encode(text)).transpose()
+}
+
+/// Bytes or text compressed the way `#[row_ext(compressed)]` decompresses
+/// them.
+#[cfg(feature = "compress")]
+pub fn compress(value: Option<&[u8]>) -> Option<Vec<u8>> {
+    value.map(crate::compress)
+}
+
+/// Text compressed in the encoding of `#[row_ext(compressed = "...")]`.
+#[cfg(feature = "compress")]
+pub fn compress_text(
+    text: Option<&str>,
+    compressed: crate::__private::Compressed,
+) -> Option<Vec<u8>> {
+    use crate::__private::Compressed;
+
+    text.map(|text| match compressed {
+        Compressed::Utf16 => crate::compress_text(text),
+        Compressed::Utf8 | Compressed::Bytes => crate::compress(text.as_bytes()),
+    })
+}
+
+fn quote(column: &str) -> String {
+    format!("[{}]", column.replace(']', "]]"))
+}
+
+/// An owned parameter value, lent to tiberius when the query is sent.
+#[derive(Debug, Clone)]
+struct Param(SqlValue);
+
+impl ToSql for Param {
+    fn to_sql(&self) -> ColumnData<'_> {
+        match &self.0 {
+            ColumnData::String(v) => ColumnData::String(v.as_deref().map(Cow::Borrowed)),
+            ColumnData::Binary(v) => ColumnData::Binary(v.as_deref().map(Cow::Borrowed)),
+            ColumnData::Xml(v) => ColumnData::Xml(v.as_deref().map(Cow::Borrowed)),
+            val => val.clone(),
+        }
+    }
+}
+
+impl<'a> IntoSql<'a> for Param {
+    fn into_sql(self) -> ColumnData<'a> {
+        self.0
+    }
+}
+
+pub(crate) fn into_owned(val: ColumnData<'_>) -> SqlValue {
+    match val {
+        ColumnData::U8(v) => ColumnData::U8(v),
+        ColumnData::I16(v) => ColumnData::I16(v),
+        ColumnData::I32(v) => ColumnData::I32(v),
+        ColumnData::I64(v) => ColumnData::I64(v),
+        ColumnData::F32(v) => ColumnData::F32(v),
+        ColumnData::F64(v) => ColumnData::F64(v),
+        ColumnData::Bit(v) => ColumnData::Bit(v),
+        ColumnData::String(v) => ColumnData::String(v.map(|v| Cow::Owned(v.into_owned()))),
+        ColumnData::Guid(v) => ColumnData::Guid(v),
+        ColumnData::Binary(v) => ColumnData::Binary(v.map(|v| Cow::Owned(v.into_owned()))),
+        ColumnData::Numeric(v) => ColumnData::Numeric(v),
+        ColumnData::Xml(v) => ColumnData::Xml(v.map(|v| Cow::Owned(v.into_owned()))),
+        ColumnData::DateTime(v) => ColumnData::DateTime(v),
+        ColumnData::SmallDateTime(v) => ColumnData::SmallDateTime(v),
+        ColumnData::Time(v) => ColumnData::Time(v),
+        ColumnData::Date(v) => ColumnData::Date(v),
+        ColumnData::DateTime2(v) => ColumnData::DateTime2(v),
+        ColumnData::DateTimeOffset(v) => ColumnData::DateTimeOffset(v),
+    }
+}
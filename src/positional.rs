@@ -0,0 +1,92 @@
+//! [`FromRow`] for single values and tuples, which read the columns of a row
+//! by position rather than by name.
+
+use anyhow::{anyhow, Context as _, Result};
+use tiberius::Column;
+
+use crate::__private::{Cells, Field};
+use crate::mapper::check;
+use crate::{FromRow, SqlDecode};
+
+/// The names of the positional fields in errors: a tuple's `.0`, `.1` and
+/// so on.
+const NAMES: [&str; 16] = [
+    "0", "1", "2", "3", "4", "5", "6", "7", "8", "9", "10", "11", "12", "13", "14", "15",
+];
+
+/// Decodes the column at `index` like a struct field: a missing column is
+/// an error unless `T` has a value for it, such as `None`.
+fn decode_nth<T: SqlDecode>(cells: &Cells<'_, '_>, index: usize) -> Result<T> {
+    match cells.nth(index) {
+        Some((col, val)) => {
+            T::decode(val).with_context(|| format!("failed to decode column `{}`", col.name()))
+        }
+        None => T::missing().ok_or_else(|| anyhow!("row has no column {}", index)),
+    }
+}
+
+/// Positional fields take the columns in order; extra columns are ignored.
+fn resolve(columns: &[Column], fields: &[Field<'_>]) -> Result<Vec<Option<usize>>> {
+    fields
+        .iter()
+        .map(|field| match columns.get(field.index) {
+            Some(column) => check(column, field).map(|_| Some(field.index)),
+            None => Ok(None),
+        })
+        .collect()
+}
+
+impl<T: SqlDecode> FromRow for T {
+    const FIELDS: &'static [Field<'static>] = &[Field {
+        accepts: T::accepts,
+        ..Field::new(0, NAMES[0], NAMES[0])
+    }];
+
+    fn decode_row(cells: &Cells<'_, '_>) -> Result<Self> {
+        decode_nth(cells, 0)
+    }
+
+    fn resolve(columns: &[Column]) -> Result<Vec<Option<usize>>> {
+        resolve(columns, Self::FIELDS)
+    }
+}
+
+macro_rules! tuple_from_row {
+    ($(($($ty:ident $index:tt),+))*) => {
+        $(
+            impl<$($ty: SqlDecode),+> FromRow for ($($ty,)+) {
+                const FIELDS: &'static [Field<'static>] = &[$(Field {
+                    accepts: $ty::accepts,
+                    ..Field::new($index, NAMES[$index], NAMES[$index])
+                }),+];
+
+                fn decode_row(cells: &Cells<'_, '_>) -> Result<Self> {
+                    Ok(($(decode_nth::<$ty>(cells, $index)?,)+))
+                }
+
+                fn resolve(columns: &[Column]) -> Result<Vec<Option<usize>>> {
+                    resolve(columns, Self::FIELDS)
+                }
+            }
+        )*
+    };
+}
+
+tuple_from_row! {
+    (A 0)
+    (A 0, B 1)
+    (A 0, B 1, C 2)
+    (A 0, B 1, C 2, D 3)
+    (A 0, B 1, C 2, D 3, E 4)
+    (A 0, B 1, C 2, D 3, E 4, F 5)
+    (A 0, B 1, C 2, D 3, E 4, F 5, G 6)
+    (A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7)
+    (A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7, I 8)
+    (A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7, I 8, J 9)
+    (A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7, I 8, J 9, K 10)
+    (A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7, I 8, J 9, K 10, L 11)
+    (A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7, I 8, J 9, K 10, L 11, M 12)
+    (A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7, I 8, J 9, K 10, L 11, M 12, N 13)
+    (A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7, I 8, J 9, K 10, L 11, M 12, N 13, O 14)
+    (A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7, I 8, J 9, K 10, L 11, M 12, N 13, O 14, P 15)
+}
@@ -0,0 +1,76 @@
+//! Calling stored procedures described by a struct, with
+//! [`Procedure`](derive@crate::Procedure).
+
+use anyhow::{bail, Result};
+use futures_util::io::{AsyncRead, AsyncWrite};
+use tiberius::{Client, Row};
+
+use crate::__private::{decode_field, Cells, Field};
+use crate::Params;
+
+/// A stored procedure call: its parameters, the OUTPUT ones among them, and
+/// the result sets it returns. Derive it with
+/// [`Procedure`](derive@crate::Procedure).
+pub trait Procedure: Sized {
+    /// The procedure's result sets, such as `(Vec<Order>, Option<Totals>)`,
+    /// `Vec<Order>` for a single one or `()` for none.
+    type Results;
+
+    #[doc(hidden)]
+    const NAME: &'static str;
+
+    /// The batch declaring the OUTPUT variables, running the procedure and
+    /// selecting its RETURN status and OUTPUT values as a last result set.
+    #[doc(hidden)]
+    const SQL: &'static str;
+
+    #[doc(hidden)]
+    fn params(&self) -> Params;
+
+    #[doc(hidden)]
+    fn read_outputs(&mut self, cells: &Cells<'_, '_>) -> Result<()>;
+
+    #[doc(hidden)]
+    fn decode_results(results: Vec<Vec<Row>>) -> Result<Self::Results>;
+}
+
+/// What a procedure call returned.
+#[derive(Debug)]
+pub struct ProcResponse<P, R> {
+    /// The parameters, with the OUTPUT fields set to the values the
+    /// procedure assigned.
+    pub params: P,
+    pub results: R,
+    /// The value of the procedure's `RETURN` statement, 0 without one.
+    pub return_status: i32,
+}
+
+/// Runs the procedure and decodes everything it returns.
+pub async fn exec_procedure<P, S>(
+    client: &mut Client<S>,
+    mut procedure: P,
+) -> Result<ProcResponse<P, P::Results>>
+where
+    P: Procedure,
+    S: AsyncRead + AsyncWrite + Unpin + Send,
+{
+    let params = procedure.params();
+    let stream = client.query(P::SQL, &params.as_refs()).await?;
+    let mut results = crate::results::collect(stream).await?;
+    let last = results.pop();
+    let outputs = match last.as_deref() {
+        Some([row]) => row.cells().collect::<Vec<_>>(),
+        _ => bail!("procedure `{}` did not return its OUTPUT values", P::NAME),
+    };
+    let cells = Cells::new(&outputs);
+    let return_status = decode_field(&cells, &Field::new(0, "return_status", RETURN_STATUS))?;
+    procedure.read_outputs(&cells)?;
+    Ok(ProcResponse {
+        results: P::decode_results(results)?,
+        params: procedure,
+        return_status,
+    })
+}
+
+/// The column of the RETURN status in the last result set.
+const RETURN_STATUS: &str = "__return_status";
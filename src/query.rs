@@ -0,0 +1,120 @@
+//! Running a query and decoding its first result set in one call.
+//!
+//! ```ignore
+//! let users: Vec<User> = query_all(&mut client, "SELECT * FROM users", &[]).await?;
+//! let user: User = query_one(&mut client, "SELECT * FROM users WHERE id = @P1", &[&id]).await?;
+//! let count: i64 = query_one(&mut client, "SELECT COUNT(*) FROM users", &[]).await?;
+//! ```
+
+use std::borrow::Cow;
+
+use anyhow::{bail, Result};
+use futures_util::io::{AsyncRead, AsyncWrite};
+use futures_util::StreamExt as _;
+use tiberius::{Client, Row, ToSql};
+
+use crate::{FromRow, FromRows, RowStream};
+
+/// Runs the query and decodes its first result set into `T`, such as
+/// `Vec<User>` or `Option<User>`.
+pub async fn query_as<'a, T, S>(
+    client: &'a mut Client<S>,
+    sql: impl Into<Cow<'a, str>>,
+    params: &'a [&'a dyn ToSql],
+) -> Result<T>
+where
+    T: FromRows,
+    S: AsyncRead + AsyncWrite + Unpin + Send,
+{
+    T::from_rows(rows(client, sql, params).await?)
+}
+
+/// Runs the query and decodes every row of its first result set.
+pub async fn query_all<'a, T, S>(
+    client: &'a mut Client<S>,
+    sql: impl Into<Cow<'a, str>>,
+    params: &'a [&'a dyn ToSql],
+) -> Result<Vec<T>>
+where
+    T: FromRow,
+    S: AsyncRead + AsyncWrite + Unpin + Send,
+{
+    query_as(client, sql, params).await
+}
+
+/// Runs the query and decodes its only row, failing when it returns no
+/// rows or more than one.
+pub async fn query_one<'a, T, S>(
+    client: &'a mut Client<S>,
+    sql: impl Into<Cow<'a, str>>,
+    params: &'a [&'a dyn ToSql],
+) -> Result<T>
+where
+    T: FromRow,
+    S: AsyncRead + AsyncWrite + Unpin + Send,
+{
+    match at_most_one(client, sql, params, "one").await? {
+        Some(row) => Ok(row),
+        None => bail!("query returned no rows"),
+    }
+}
+
+/// Runs the query and decodes its row, if any, failing when it returns
+/// more than one.
+pub async fn query_optional<'a, T, S>(
+    client: &'a mut Client<S>,
+    sql: impl Into<Cow<'a, str>>,
+    params: &'a [&'a dyn ToSql],
+) -> Result<Option<T>>
+where
+    T: FromRow,
+    S: AsyncRead + AsyncWrite + Unpin + Send,
+{
+    at_most_one(client, sql, params, "at most one").await
+}
+
+/// Runs the query and decodes the rows of its first result set as they
+/// arrive. See [`RowStream`].
+pub async fn query_stream<'a, T, S>(
+    client: &'a mut Client<S>,
+    sql: impl Into<Cow<'a, str>>,
+    params: &'a [&'a dyn ToSql],
+) -> Result<RowStream<'a, T>>
+where
+    T: FromRow,
+    S: AsyncRead + AsyncWrite + Unpin + Send,
+{
+    Ok(RowStream::new(client.query(sql, params).await?))
+}
+
+/// The first row of the query's first result set, if any. Reading stops as
+/// soon as a second row arrives, so an unexpectedly large result isn't
+/// buffered before failing.
+async fn at_most_one<'a, T, S>(
+    client: &'a mut Client<S>,
+    sql: impl Into<Cow<'a, str>>,
+    params: &'a [&'a dyn ToSql],
+    expected: &str,
+) -> Result<Option<T>>
+where
+    T: FromRow,
+    S: AsyncRead + AsyncWrite + Unpin + Send,
+{
+    let mut rows = query_stream::<T, S>(client, sql, params).await?;
+    let row = rows.next().await.transpose()?;
+    if rows.next().await.is_some() {
+        bail!("query returned more than one row, expected {}", expected);
+    }
+    Ok(row)
+}
+
+async fn rows<'a, S>(
+    client: &'a mut Client<S>,
+    sql: impl Into<Cow<'a, str>>,
+    params: &'a [&'a dyn ToSql],
+) -> Result<Vec<Row>>
+where
+    S: AsyncRead + AsyncWrite + Unpin + Send,
+{
+    Ok(client.query(sql, params).await?.into_first_result().await?)
+}
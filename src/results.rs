@@ -0,0 +1,102 @@
+//! Decoding the several result sets of a batch or stored procedure into a
+//! tuple, such as a header, its lines and totals.
+
+use std::borrow::Cow;
+
+use anyhow::{bail, Context as _, Result};
+use futures_util::io::{AsyncRead, AsyncWrite};
+use futures_util::TryStreamExt as _;
+use tiberius::{Client, QueryItem, QueryStream, Row, ToSql};
+
+use crate::FromRows;
+
+/// A tuple of [`FromRows`] types, one for each result set in order, such as
+/// `(Vec<Order>, Vec<OrderLine>, Option<Totals>)`.
+pub trait FromResults: Sized {
+    fn from_results(results: Vec<Vec<Row>>) -> Result<Self>;
+}
+
+/// Reads every result set of `stream` and decodes them into `T`.
+///
+/// ```ignore
+/// let stream = client.query("EXEC dbo.GetOrder @P1", &[&id]).await?;
+/// let (header, lines, totals): (Option<Order>, Vec<OrderLine>, Option<Totals>) =
+///     from_results(stream).await?;
+/// ```
+pub async fn from_results<T: FromResults>(stream: QueryStream<'_>) -> Result<T> {
+    T::from_results(collect(stream).await?)
+}
+
+/// Runs the query and decodes every result set into `T`. See
+/// [`from_results`].
+pub async fn query_results<'a, T, S>(
+    client: &'a mut Client<S>,
+    sql: impl Into<Cow<'a, str>>,
+    params: &'a [&'a dyn ToSql],
+) -> Result<T>
+where
+    T: FromResults,
+    S: AsyncRead + AsyncWrite + Unpin + Send,
+{
+    from_results(client.query(sql, params).await?).await
+}
+
+/// The rows of each result set. Unlike `QueryStream::into_results`, a
+/// result set without rows is kept, so the sets keep their positions.
+pub(crate) async fn collect(mut stream: QueryStream<'_>) -> Result<Vec<Vec<Row>>> {
+    let mut results = Vec::<Vec<Row>>::new();
+    while let Some(item) = stream.try_next().await? {
+        match item {
+            QueryItem::Metadata(_) => results.push(Vec::new()),
+            QueryItem::Row(row) => match results.last_mut() {
+                Some(rows) => rows.push(row),
+                None => results.push(vec![row]),
+            },
+        }
+    }
+    Ok(results)
+}
+
+/// No result sets, as for a procedure that only sets OUTPUT parameters.
+impl FromResults for () {
+    fn from_results(results: Vec<Vec<Row>>) -> Result<Self> {
+        if !results.is_empty() {
+            bail!("query returned {} result sets, expected 0", results.len());
+        }
+        Ok(())
+    }
+}
+
+macro_rules! tuple_from_results {
+    ($($len:literal => ($($ty:ident $index:tt),+))*) => {
+        $(
+            impl<$($ty: FromRows),+> FromResults for ($($ty,)+) {
+                fn from_results(results: Vec<Vec<Row>>) -> Result<Self> {
+                    if results.len() != $len {
+                        bail!(
+                            "query returned {} result sets, expected {}",
+                            results.len(),
+                            $len
+                        );
+                    }
+                    let mut results = results.into_iter();
+                    Ok(($(
+                        $ty::from_rows(results.next().unwrap())
+                            .with_context(|| format!("failed to decode result set {}", $index))?,
+                    )+))
+                }
+            }
+        )*
+    };
+}
+
+tuple_from_results! {
+    1 => (A 0)
+    2 => (A 0, B 1)
+    3 => (A 0, B 1, C 2)
+    4 => (A 0, B 1, C 2, D 3)
+    5 => (A 0, B 1, C 2, D 3, E 4)
+    6 => (A 0, B 1, C 2, D 3, E 4, F 5)
+    7 => (A 0, B 1, C 2, D 3, E 4, F 5, G 6)
+    8 => (A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7)
+}
@@ -0,0 +1,114 @@
+//! Decoding delimited text columns into collections, for
+//! `#[row_ext(split = "...")]`.
+
+use std::{
+    collections::{BTreeSet, HashSet},
+    fmt::Display,
+    hash::Hash,
+    str::FromStr,
+};
+
+use anyhow::{anyhow, bail, Context as _, Result};
+use tiberius::ColumnData;
+
+use crate::{
+    decode::{column_type, is_null},
+    normalize::Trim,
+};
+
+/// A collection a `#[row_ext(split)]` field can decode into.
+///
+/// A NULL column decodes into an empty collection, or `None` for `Option`.
+/// An empty one always decodes into an empty collection.
+pub trait SplitCollection: Sized {
+    type Item;
+
+    /// Builds the collection from the parsed elements, `None` for NULL.
+    fn from_items(items: Option<Vec<Self::Item>>) -> Self;
+
+    /// The value to use when the column is not part of the row at all, like
+    /// [`SqlDecode::missing`].
+    fn missing() -> Option<Self> {
+        None
+    }
+}
+
+impl<T> SplitCollection for Vec<T> {
+    type Item = T;
+
+    fn from_items(items: Option<Vec<T>>) -> Self {
+        items.unwrap_or_default()
+    }
+}
+
+impl<T: Eq + Hash> SplitCollection for HashSet<T> {
+    type Item = T;
+
+    fn from_items(items: Option<Vec<T>>) -> Self {
+        items.into_iter().flatten().collect()
+    }
+}
+
+impl<T: Ord> SplitCollection for BTreeSet<T> {
+    type Item = T;
+
+    fn from_items(items: Option<Vec<T>>) -> Self {
+        items.into_iter().flatten().collect()
+    }
+}
+
+impl<C: SplitCollection> SplitCollection for Option<C> {
+    type Item = C::Item;
+
+    fn from_items(items: Option<Vec<C::Item>>) -> Self {
+        items.map(|items| C::from_items(Some(items)))
+    }
+
+    fn missing() -> Option<Self> {
+        Some(None)
+    }
+}
+
+/// How the column text is split into elements.
+pub struct Split<'a> {
+    pub sep: &'a str,
+    /// Trim whitespace around each element.
+    pub trim: Option<Trim>,
+    /// Drop elements that are empty, after trimming.
+    pub skip_empty: bool,
+}
+
+/// Parses each element with [`FromStr`]. Errors name the failing element by
+/// its position in the column text. Empty text, after trimming, has no
+/// elements rather than a single empty one.
+pub(crate) fn decode<C>(val: &ColumnData<'static>, split: &Split<'_>) -> Result<C>
+where
+    C: SplitCollection,
+    C::Item: FromStr,
+    <C::Item as FromStr>::Err: Display,
+{
+    let text = match val {
+        ColumnData::String(Some(v)) => v,
+        _ if is_null(val) => return Ok(C::from_items(None)),
+        _ => bail!("cannot split {}", column_type(val)),
+    };
+    if split
+        .trim
+        .map_or(text.as_ref(), |trim| trim.apply(text))
+        .is_empty()
+    {
+        return Ok(C::from_items(Some(Vec::new())));
+    }
+    let items = text
+        .split(split.sep)
+        .map(|item| split.trim.map_or(item, |trim| trim.apply(item)))
+        .enumerate()
+        .filter(|(_, item)| !(split.skip_empty && item.is_empty()))
+        .map(|(i, item)| {
+            item.parse()
+                .map_err(|e| anyhow!("{}", e))
+                .with_context(|| format!("element {} (`{}`)", i, item))
+        })
+        .collect::<Result<Vec<_>>>()?;
+    Ok(C::from_items(Some(items)))
+}
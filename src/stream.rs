@@ -0,0 +1,134 @@
+//! Decoding the rows of a query as they arrive, for result sets too large to
+//! buffer with `into_first_result`.
+
+use std::pin::Pin;
+use std::task::{ready, Context, Poll};
+
+use anyhow::Result;
+use futures_util::{Stream, StreamExt as _};
+use tiberius::{QueryItem, QueryStream};
+
+use crate::{FromRow, RowMapper};
+
+/// The rows of a query's first result set, decoded into `T` one at a time.
+///
+/// Each row is decoded as soon as the server sends it through a
+/// [`RowMapper`] built from the result set's metadata, so only one row is
+/// held in memory. A row that fails to decode yields an error and the
+/// stream goes on with the next one; an error reading the stream ends it.
+///
+/// # Cancellation
+///
+/// Dropping the stream early doesn't cancel the query, as tiberius has no way
+/// to send the attention signal that would. The server keeps sending the
+/// rest of the response, and the next call on the client reads and discards
+/// all of it before running, so a large query still streams to completion.
+/// The connection stays usable. To stop reading early, bound the query in
+/// SQL with `TOP` or `OFFSET ... FETCH`, or close the connection, which ends
+/// the query on the server.
+///
+/// ```ignore
+/// let stream = client.query("SELECT * FROM orders", &[]).await?;
+/// let mut orders = RowStream::<Order>::new(stream).chunks(1000);
+/// while let Some(batch) = orders.next().await {
+///     export(batch?).await?;
+/// }
+/// ```
+pub struct RowStream<'a, T> {
+    stream: QueryStream<'a>,
+    mapper: Option<RowMapper<T>>,
+    done: bool,
+}
+
+impl<'a, T: FromRow> RowStream<'a, T> {
+    pub fn new(stream: QueryStream<'a>) -> Self {
+        RowStream {
+            stream,
+            mapper: None,
+            done: false,
+        }
+    }
+
+    /// Batches of up to `size` rows. The last batch holds what is left.
+    ///
+    /// # Panics
+    ///
+    /// If `size` is zero.
+    pub fn chunks(self, size: usize) -> Chunks<'a, T> {
+        assert!(size > 0, "chunk size must be greater than zero");
+        Chunks {
+            rows: self,
+            size,
+            chunk: Vec::with_capacity(size),
+        }
+    }
+}
+
+impl<T: FromRow> Stream for RowStream<'_, T> {
+    type Item = Result<T>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+        while !this.done {
+            match ready!(this.stream.poll_next_unpin(cx)) {
+                Some(Ok(QueryItem::Metadata(meta))) => {
+                    // Only the first result set is read.
+                    if this.mapper.is_some() {
+                        break;
+                    }
+                    match RowMapper::new(meta.columns()) {
+                        Ok(mapper) => this.mapper = Some(mapper),
+                        Err(e) => {
+                            this.done = true;
+                            return Poll::Ready(Some(Err(e)));
+                        }
+                    }
+                }
+                Some(Ok(QueryItem::Row(row))) => {
+                    let decoded = match &this.mapper {
+                        Some(mapper) => mapper.decode(&row),
+                        None => T::from_row_ref(&row),
+                    };
+                    return Poll::Ready(Some(decoded));
+                }
+                Some(Err(e)) => {
+                    this.done = true;
+                    return Poll::Ready(Some(Err(e.into())));
+                }
+                None => break,
+            }
+        }
+        this.done = true;
+        Poll::Ready(None)
+    }
+}
+
+/// Batches of rows from [`RowStream::chunks`].
+pub struct Chunks<'a, T> {
+    rows: RowStream<'a, T>,
+    size: usize,
+    chunk: Vec<T>,
+}
+
+impl<T: FromRow + Unpin> Stream for Chunks<'_, T> {
+    type Item = Result<Vec<T>>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+        loop {
+            match ready!(this.rows.poll_next_unpin(cx)) {
+                Some(Ok(row)) => {
+                    this.chunk.push(row);
+                    if this.chunk.len() == this.size {
+                        let chunk =
+                            std::mem::replace(&mut this.chunk, Vec::with_capacity(this.size));
+                        return Poll::Ready(Some(Ok(chunk)));
+                    }
+                }
+                Some(Err(e)) => return Poll::Ready(Some(Err(e))),
+                None if this.chunk.is_empty() => return Poll::Ready(None),
+                None => return Poll::Ready(Some(Ok(std::mem::take(&mut this.chunk)))),
+            }
+        }
+    }
+}
@@ -0,0 +1,63 @@
+//! Time zone used to read zone-less `datetime`, `smalldatetime` and
+//! `datetime2` columns, enabled by the `chrono-tz` feature.
+//!
+//! `#[row_ext(tz = "...")]` on a field or struct takes precedence over the
+//! process-wide default from [`set_default_tz`]. Without either, such columns
+//! are read as UTC.
+
+use std::{cell::Cell, sync::RwLock};
+
+use anyhow::{bail, Result};
+use chrono::{DateTime, LocalResult, NaiveDateTime, TimeZone as _};
+use chrono_tz::Tz;
+
+static DEFAULT_TZ: RwLock<Option<Tz>> = RwLock::new(None);
+
+thread_local! {
+    static SCOPED_TZ: Cell<Option<Tz>> = const { Cell::new(None) };
+}
+
+/// Sets the time zone zone-less columns are read in when no
+/// `#[row_ext(tz)]` applies.
+pub fn set_default_tz(tz: Tz) {
+    *DEFAULT_TZ.write().unwrap() = Some(tz);
+}
+
+/// The time zone set with [`set_default_tz`], if any.
+pub fn default_tz() -> Option<Tz> {
+    *DEFAULT_TZ.read().unwrap()
+}
+
+/// The time zone in effect for the value being decoded.
+pub(crate) fn current_tz() -> Option<Tz> {
+    SCOPED_TZ.get().or_else(default_tz)
+}
+
+/// The time zone of a `#[row_ext(tz)]`, whose name the derive has checked.
+pub fn parse_tz(name: &str) -> Tz {
+    name.parse()
+        .unwrap_or_else(|_| panic!("unknown time zone `{}`", name))
+}
+
+/// Runs `f` with `tz` as the time zone in effect, for `#[row_ext(tz)]`.
+pub fn with_tz<T>(tz: Tz, f: impl FnOnce() -> Result<T>) -> Result<T> {
+    struct Restore(Option<Tz>);
+
+    impl Drop for Restore {
+        fn drop(&mut self) {
+            SCOPED_TZ.set(self.0);
+        }
+    }
+
+    let _restore = Restore(SCOPED_TZ.replace(Some(tz)));
+    f()
+}
+
+/// Reads `naive` as a local time in `tz`. A time repeated by a DST change
+/// resolves to its first occurrence, a time skipped by one is an error.
+pub(crate) fn localize(naive: NaiveDateTime, tz: Tz) -> Result<DateTime<Tz>> {
+    match tz.from_local_datetime(&naive) {
+        LocalResult::Single(dt) | LocalResult::Ambiguous(dt, _) => Ok(dt),
+        LocalResult::None => bail!("{} does not exist in {} due to a DST gap", naive, tz),
+    }
+}
@@ -0,0 +1,29 @@
+//! The result of `update_from_row`, generated with `#[row_ext(update)]`.
+
+/// The fields an `update_from_row` call changed, by field name.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ChangedFields(Vec<&'static str>);
+
+impl ChangedFields {
+    #[doc(hidden)]
+    pub fn push(&mut self, field: &'static str) {
+        self.0.push(field);
+    }
+
+    pub fn contains(&self, field: &str) -> bool {
+        self.0.contains(&field)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// The changed fields, in declaration order.
+    pub fn iter(&self) -> impl Iterator<Item = &'static str> + '_ {
+        self.0.iter().copied()
+    }
+}
@@ -0,0 +1,78 @@
+//! Decoding XML columns, enabled by the `xml` feature.
+
+use std::fmt;
+
+use anyhow::{bail, Context as _, Result};
+use serde::de::{DeserializeOwned, IntoDeserializer as _};
+use tiberius::ColumnData;
+
+use crate::{
+    decode::{column_type, is_null, mismatch},
+    SqlDecode,
+};
+
+/// The XML text of a column, or `None` for NULL.
+fn text<'a>(val: &'a ColumnData<'static>) -> Result<Option<&'a str>> {
+    match val {
+        ColumnData::Xml(Some(v)) => Ok(Some(v.as_ref().as_ref())),
+        ColumnData::String(Some(v)) => Ok(Some(v)),
+        _ if is_null(val) => Ok(None),
+        _ => bail!("cannot decode {} as XML", column_type(val)),
+    }
+}
+
+/// Deserializes an XML or text column with quick-xml, for
+/// `#[row_ext(xml)]`. A NULL or missing column only decodes into `Option`.
+pub(crate) fn decode<T: DeserializeOwned>(val: Option<&ColumnData<'static>>) -> Result<T> {
+    match val.map(text).transpose()?.flatten() {
+        Some(text) => quick_xml::de::from_str(text)
+            .with_context(|| format!("XML does not match `{}`", std::any::type_name::<T>())),
+        None => T::deserialize(().into_deserializer())
+            .map_err(|_: serde::de::value::Error| mismatch::<T>(&ColumnData::Xml(None))),
+    }
+}
+
+/// An XML column kept as text, for documents without a fixed shape.
+///
+/// The text is checked to be well-formed when decoded and can be walked with
+/// [`document`](Self::document).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct XmlDocument {
+    text: String,
+}
+
+impl XmlDocument {
+    /// Checks that `text` is a well-formed XML document.
+    pub fn parse(text: String) -> Result<Self> {
+        roxmltree::Document::parse(&text)?;
+        Ok(Self { text })
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.text
+    }
+
+    pub fn into_string(self) -> String {
+        self.text
+    }
+
+    /// The parsed document tree.
+    pub fn document(&self) -> roxmltree::Document<'_> {
+        roxmltree::Document::parse(&self.text).expect("checked in XmlDocument::parse")
+    }
+}
+
+impl fmt::Display for XmlDocument {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.text)
+    }
+}
+
+impl SqlDecode for XmlDocument {
+    fn decode(val: &ColumnData<'static>) -> Result<Self> {
+        match text(val)? {
+            Some(text) => XmlDocument::parse(text.to_string()),
+            None => Err(mismatch::<Self>(val)),
+        }
+    }
+}
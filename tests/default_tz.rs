@@ -0,0 +1,29 @@
+//! Kept out of `testm` because the default time zone is process-wide and
+//! would leak into the other decoding tests.
+#![cfg(feature = "chrono-tz")]
+
+use chrono::{DateTime, NaiveDate, Utc};
+use tiberius::{
+    time::{Date, DateTime2, Time},
+    ColumnData,
+};
+use tiberius_row::SqlDecode;
+
+#[test]
+fn test_default_tz() -> anyhow::Result<()> {
+    // 2023-01-01 12:30.
+    let val = ColumnData::DateTime2(Some(DateTime2::new(
+        Date::new(738_520),
+        Time::new(45_000 * 10_000_000, 7),
+    )));
+    let expected = NaiveDate::from_ymd_opt(2023, 1, 1)
+        .unwrap()
+        .and_hms_opt(4, 30, 0)
+        .unwrap()
+        .and_utc();
+
+    assert_eq!(tiberius_row::default_tz(), None);
+    tiberius_row::set_default_tz(chrono_tz::Asia::Shanghai);
+    assert_eq!(DateTime::<Utc>::decode(&val)?, expected);
+    Ok(())
+}
@@ -0,0 +1,58 @@
+use std::borrow::Cow;
+
+use tiberius::ColumnData;
+use tiberius_row::Row_Ext;
+
+use crate::helpers::cells;
+
+#[derive(Debug, Row_Ext)]
+struct LogLine<'a> {
+    id: i64,
+    message: &'a str,
+    payload: &'a [u8],
+    #[row_ext(rename = "Source")]
+    source: Option<&'a str>,
+    #[row_ext(borrow)]
+    host: Cow<'a, str>,
+}
+
+#[derive(Debug, Row_Ext)]
+struct LogId {
+    id: i64,
+}
+
+#[test]
+fn test_borrowed_fields() -> anyhow::Result<()> {
+    let cells = cells(vec![
+        ("id", ColumnData::I64(Some(7))),
+        ("message", ColumnData::String(Some("started".into()))),
+        ("payload", ColumnData::Binary(Some(vec![1, 2, 3].into()))),
+        ("Source", ColumnData::String(None)),
+        ("host", ColumnData::String(Some("db01".into()))),
+    ]);
+    let line = LogLine::from_cells(cells.iter().map(|(c, v)| (c, v)))?;
+    let ColumnData::String(Some(message)) = &cells[1].1 else {
+        unreachable!()
+    };
+    assert_eq!(line.id, 7);
+    assert!(std::ptr::eq(line.message, message.as_ref()));
+    assert_eq!(line.payload, [1, 2, 3]);
+    assert_eq!(line.source, None);
+    assert!(matches!(line.host, Cow::Borrowed("db01")));
+
+    // The same cells decode again into another view.
+    let id = LogId::from_cells(cells.iter().map(|(c, v)| (c, v)))?;
+    assert_eq!(id.id, 7);
+    Ok(())
+}
+
+#[test]
+fn test_borrowed_field_error() {
+    let cells = cells(vec![
+        ("id", ColumnData::I64(Some(7))),
+        ("message", ColumnData::String(None)),
+    ]);
+    let err = LogLine::from_cells(cells.iter().map(|(c, v)| (c, v))).unwrap_err();
+    assert_eq!(err.to_string(), "failed to decode column `message`");
+    assert_eq!(err.root_cause().to_string(), "unexpected NULL for `&str`");
+}
@@ -0,0 +1,86 @@
+use serde::Deserialize;
+use tiberius::ColumnData;
+use tiberius_row::{compress, compress_text, Row_Ext};
+
+use crate::helpers::cells;
+
+#[derive(Debug, PartialEq, Deserialize)]
+struct Payload {
+    id: i32,
+    tags: Vec<String>,
+}
+
+#[allow(unused)]
+#[derive(Debug, Row_Ext)]
+struct Archive {
+    #[row_ext(compressed = "utf-16")]
+    body: String,
+    #[row_ext(compressed = "utf-8")]
+    summary: Option<String>,
+    #[row_ext(compressed)]
+    raw: Vec<u8>,
+    #[row_ext(json, compressed = "utf-16")]
+    payload: Payload,
+}
+
+fn bytes(b: Vec<u8>) -> ColumnData<'static> {
+    ColumnData::Binary(Some(b.into()))
+}
+
+#[test]
+fn test_compressed() -> anyhow::Result<()> {
+    let cells = cells(vec![
+        ("body", bytes(compress_text("压缩的正文"))),
+        ("summary", bytes(compress("short".as_bytes()))),
+        ("raw", bytes(compress(&[0, 1, 2, 255]))),
+        (
+            "payload",
+            bytes(compress_text(r#"{"id": 7, "tags": ["a", "b"]}"#)),
+        ),
+    ]);
+    let archive = Archive::from_cells(cells.iter().map(|(c, v)| (c, v)))?;
+    assert_eq!(archive.body, "压缩的正文");
+    assert_eq!(archive.summary.as_deref(), Some("short"));
+    assert_eq!(archive.raw, [0, 1, 2, 255]);
+    assert_eq!(
+        archive.payload,
+        Payload {
+            id: 7,
+            tags: vec!["a".into(), "b".into()]
+        }
+    );
+    Ok(())
+}
+
+#[test]
+fn test_compressed_null() -> anyhow::Result<()> {
+    let cells = cells(vec![
+        ("body", bytes(compress_text(""))),
+        ("summary", ColumnData::Binary(None)),
+        ("raw", bytes(compress(&[]))),
+        ("payload", bytes(compress_text(r#"{"id": 1, "tags": []}"#))),
+    ]);
+    let archive = Archive::from_cells(cells.iter().map(|(c, v)| (c, v)))?;
+    assert_eq!(archive.body, "");
+    assert_eq!(archive.summary, None);
+    assert!(archive.raw.is_empty());
+    Ok(())
+}
+
+#[test]
+fn test_compressed_error() {
+    let cells = cells(vec![
+        ("body", bytes(b"not gzip".to_vec())),
+        ("raw", ColumnData::Binary(None)),
+    ]);
+    let err = Archive::from_cells(cells.iter().map(|(c, v)| (c, v))).unwrap_err();
+    assert_eq!(err.to_string(), "failed to decode column `body`");
+    assert_eq!(err.chain().nth(1).unwrap().to_string(), "invalid GZip data");
+}
+
+#[test]
+fn test_compressed_text_error() {
+    let cells = cells(vec![("body", bytes(compress(&[0x41, 0x00, 0x42])))]);
+    let err = Archive::from_cells(cells.iter().map(|(c, v)| (c, v))).unwrap_err();
+    assert_eq!(err.root_cause().to_string(), "invalid UTF-16 text");
+}
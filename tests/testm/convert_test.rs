@@ -0,0 +1,111 @@
+use std::net::IpAddr;
+
+use tiberius::ColumnData;
+use tiberius_row::Row_Ext;
+
+use crate::helpers::cells;
+
+#[derive(Debug, PartialEq)]
+struct Port(u16);
+
+impl TryFrom<i32> for Port {
+    type Error = String;
+
+    fn try_from(v: i32) -> Result<Self, Self::Error> {
+        u16::try_from(v)
+            .map(Port)
+            .map_err(|_| format!("{} is not a valid port", v))
+    }
+}
+
+#[allow(unused)]
+#[derive(Debug, Row_Ext)]
+struct Server {
+    #[row_ext(from_str)]
+    address: IpAddr,
+    #[row_ext(from_str, trim)]
+    weight: f64,
+    #[row_ext(from_str)]
+    gateway: Option<IpAddr>,
+    #[row_ext(try_from = "i32")]
+    port: Port,
+    #[row_ext(try_from = "i32")]
+    admin_port: Option<Port>,
+}
+
+fn text(s: &str) -> ColumnData<'static> {
+    ColumnData::String(Some(s.to_string().into()))
+}
+
+#[test]
+fn test_convert() -> anyhow::Result<()> {
+    let cells = cells(vec![
+        ("address", text("10.0.0.1")),
+        ("weight", text(" 0.5 ")),
+        ("gateway", ColumnData::String(None)),
+        ("port", ColumnData::I32(Some(8080))),
+        ("admin_port", ColumnData::I32(Some(9090))),
+    ]);
+    let server = Server::from_cells(cells.iter().map(|(c, v)| (c, v)))?;
+    assert_eq!(server.address, "10.0.0.1".parse::<IpAddr>()?);
+    assert_eq!(server.weight, 0.5);
+    assert_eq!(server.gateway, None);
+    assert_eq!(server.port, Port(8080));
+    assert_eq!(server.admin_port, Some(Port(9090)));
+    Ok(())
+}
+
+#[test]
+fn test_convert_missing_option() -> anyhow::Result<()> {
+    let cells = cells(vec![
+        ("address", text("::1")),
+        ("weight", text("1")),
+        ("port", ColumnData::I32(Some(80))),
+    ]);
+    let server = Server::from_cells(cells.iter().map(|(c, v)| (c, v)))?;
+    assert_eq!(server.gateway, None);
+    assert_eq!(server.admin_port, None);
+    Ok(())
+}
+
+#[test]
+fn test_from_str_error() {
+    let cells = cells(vec![
+        ("address", text("10.0.0.300")),
+        ("weight", text("1")),
+        ("port", ColumnData::I32(Some(80))),
+    ]);
+    let err = Server::from_cells(cells.iter().map(|(c, v)| (c, v))).unwrap_err();
+    assert_eq!(err.to_string(), "failed to decode column `address`");
+    let cause = err.root_cause().to_string();
+    assert!(cause.starts_with("cannot parse `10.0.0.300` as `"));
+    assert!(cause.ends_with("IpAddr`: invalid IP address syntax"));
+}
+
+#[test]
+fn test_try_from_error() {
+    let cells = cells(vec![
+        ("address", text("10.0.0.1")),
+        ("weight", text("1")),
+        ("port", ColumnData::I32(Some(70000))),
+    ]);
+    let err = Server::from_cells(cells.iter().map(|(c, v)| (c, v))).unwrap_err();
+    assert_eq!(err.to_string(), "failed to decode column `port`");
+    let cause = err.root_cause().to_string();
+    assert!(cause.starts_with("cannot convert 70000 to `"));
+    assert!(cause.ends_with("Port`: 70000 is not a valid port"));
+}
+
+#[test]
+fn test_from_str_null() {
+    let cells = cells(vec![
+        ("address", ColumnData::String(None)),
+        ("weight", text("1")),
+        ("port", ColumnData::I32(Some(80))),
+    ]);
+    let err = Server::from_cells(cells.iter().map(|(c, v)| (c, v))).unwrap_err();
+    assert_eq!(err.to_string(), "failed to decode column `address`");
+    let cause = err.root_cause().to_string();
+    assert!(cause.starts_with("unexpected NULL for `"));
+    assert!(cause.ends_with("IpAddr`"));
+}
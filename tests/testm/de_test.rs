@@ -0,0 +1,99 @@
+use std::collections::HashMap;
+
+use serde::Deserialize;
+use tiberius::numeric::Numeric;
+use tiberius::ColumnData;
+
+use crate::helpers::cells;
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct Invoice {
+    id: i32,
+    #[serde(alias = "Customer")]
+    customer_name: String,
+    total: f64,
+    note: Option<String>,
+    #[serde(default)]
+    paid: bool,
+    status: Status,
+    #[serde(flatten)]
+    rest: HashMap<String, serde_json::Value>,
+}
+
+#[derive(Debug, PartialEq, Deserialize)]
+enum Status {
+    Open,
+    Closed,
+}
+
+#[test]
+fn test_from_row_struct() -> anyhow::Result<()> {
+    let cells = cells(vec![
+        ("Id", ColumnData::I32(Some(1))),
+        ("Customer", ColumnData::String(Some("Alice".into()))),
+        (
+            "Total",
+            ColumnData::Numeric(Some(Numeric::new_with_scale(1250, 2))),
+        ),
+        ("Note", ColumnData::String(None)),
+        ("Status", ColumnData::String(Some("Closed".into()))),
+        ("Region", ColumnData::String(Some("EU".into()))),
+    ]);
+    let invoice: Invoice = tiberius_row::from_cells(cells.iter().map(|(c, v)| (c, v)))?;
+    assert_eq!(invoice.id, 1);
+    assert_eq!(invoice.customer_name, "Alice");
+    assert_eq!(invoice.total, 12.5);
+    assert_eq!(invoice.note, None);
+    assert!(!invoice.paid);
+    assert_eq!(invoice.status, Status::Closed);
+    assert_eq!(invoice.rest["Region"], "EU");
+    assert_ne!(Status::Open, invoice.status);
+    Ok(())
+}
+
+#[test]
+fn test_from_row_map_and_tuple() -> anyhow::Result<()> {
+    let cells = cells(vec![
+        ("id", ColumnData::I64(Some(7))),
+        ("payload", ColumnData::Binary(Some(vec![1, 2, 3].into()))),
+    ]);
+    let cells = cells.iter().map(|(c, v)| (c, v)).collect::<Vec<_>>();
+    let (id, payload): (i64, Vec<u8>) = tiberius_row::from_cells(cells.clone())?;
+    assert_eq!(id, 7);
+    assert_eq!(payload, [1, 2, 3]);
+    let map: HashMap<String, serde_json::Value> = tiberius_row::from_cells(cells)?;
+    assert_eq!(map["id"], 7);
+    assert_eq!(map["payload"], serde_json::json!([1, 2, 3]));
+    Ok(())
+}
+
+#[test]
+fn test_from_row_scalar() -> anyhow::Result<()> {
+    let count = cells(vec![("count", ColumnData::I32(Some(3)))]);
+    let count: u64 = tiberius_row::from_cells(count.iter().map(|(c, v)| (c, v)))?;
+    assert_eq!(count, 3);
+    let null = cells(vec![("name", ColumnData::String(None))]);
+    let name: Option<String> = tiberius_row::from_cells(null.iter().map(|(c, v)| (c, v)))?;
+    assert_eq!(name, None);
+    Ok(())
+}
+
+#[test]
+fn test_from_row_errors() {
+    let cells = cells(vec![
+        ("Id", ColumnData::String(Some("one".into()))),
+        ("Total", ColumnData::F64(Some(1.0))),
+    ]);
+    let err = tiberius_row::from_cells::<Invoice>(cells.iter().map(|(c, v)| (c, v)))
+        .err()
+        .unwrap();
+    assert_eq!(
+        err.to_string(),
+        "failed to decode column `Id`: invalid type: string \"one\", expected i32"
+    );
+    let err = tiberius_row::from_cells::<i32>(cells.iter().map(|(c, v)| (c, v)))
+        .err()
+        .unwrap();
+    assert_eq!(err.to_string(), "expected a single column, found 2");
+}
@@ -0,0 +1,223 @@
+use chrono::{FixedOffset, NaiveDate, NaiveDateTime, NaiveTime, Utc};
+use tiberius::{
+    time::{Date, DateTime, DateTime2, DateTimeOffset, SmallDateTime, Time},
+    ColumnData,
+};
+use tiberius_row::{Row_Ext, SqlDecode};
+
+use crate::helpers::cells;
+
+/// 2023-01-01, counted in days from 0001-01-01.
+const DAYS_2023_01_01: u32 = 738_520;
+/// 12:34:56.789 at the default scale of 7 (100ns increments).
+const INCREMENTS_12_34_56_789: u64 = 452_967_890_000;
+
+fn datetime2() -> DateTime2 {
+    DateTime2::new(
+        Date::new(DAYS_2023_01_01),
+        Time::new(INCREMENTS_12_34_56_789, 7),
+    )
+}
+
+#[allow(unused)]
+#[derive(Debug, Row_Ext)]
+struct Account {
+    id: i32,
+    #[row_ext(rename = "AccountName")]
+    name: String,
+    balance: f64,
+    note: Option<String>,
+    closed_at: Option<chrono::NaiveDateTime>,
+}
+
+#[test]
+fn test_from_cells() -> anyhow::Result<()> {
+    let cells = cells(vec![
+        ("id", ColumnData::I32(Some(7))),
+        ("AccountName", ColumnData::String(Some("alice".into()))),
+        ("balance", ColumnData::F32(Some(1.5))),
+        ("note", ColumnData::String(None)),
+    ]);
+    let account = Account::from_cells(cells.iter().map(|(c, v)| (c, v)))?;
+    assert_eq!(account.id, 7);
+    assert_eq!(account.name, "alice");
+    assert_eq!(account.balance, 1.5);
+    assert_eq!(account.note, None);
+    assert_eq!(account.closed_at, None);
+    Ok(())
+}
+
+#[test]
+fn test_from_cells_errors() {
+    let missing = cells(vec![("id", ColumnData::I32(Some(7)))]);
+    let err = Account::from_cells(missing.iter().map(|(c, v)| (c, v))).unwrap_err();
+    assert_eq!(err.to_string(), "column `AccountName` not found");
+
+    let null = cells(vec![
+        ("id", ColumnData::I32(None)),
+        ("AccountName", ColumnData::String(Some("alice".into()))),
+        ("balance", ColumnData::F64(Some(0.0))),
+    ]);
+    let err = Account::from_cells(null.iter().map(|(c, v)| (c, v))).unwrap_err();
+    assert_eq!(err.to_string(), "failed to decode column `id`");
+    assert_eq!(err.root_cause().to_string(), "unexpected NULL for `i32`");
+}
+
+#[test]
+fn test_decode_datetime() -> anyhow::Result<()> {
+    let date = NaiveDate::from_ymd_opt(2023, 1, 1).unwrap();
+
+    // 1/300 second fragments since 1900-01-01.
+    let val = ColumnData::DateTime(Some(DateTime::new(44_925, 13_589_037)));
+    let expected = date.and_hms_milli_opt(12, 34, 56, 790).unwrap();
+    assert_eq!(NaiveDateTime::decode(&val)?, expected);
+    assert_eq!(chrono::DateTime::<Utc>::decode(&val)?, expected.and_utc());
+    assert_eq!(String::decode(&val)?, "2023-01-01T12:34:56.790");
+
+    // Minutes since 1900-01-01.
+    let val = ColumnData::SmallDateTime(Some(SmallDateTime::new(44_925, 754)));
+    let expected = date.and_hms_opt(12, 34, 0).unwrap();
+    assert_eq!(NaiveDateTime::decode(&val)?, expected);
+    assert_eq!(chrono::DateTime::<Utc>::decode(&val)?, expected.and_utc());
+    assert_eq!(String::decode(&val)?, "2023-01-01T12:34:00");
+
+    let val = ColumnData::DateTime2(Some(datetime2()));
+    let expected = date.and_hms_milli_opt(12, 34, 56, 789).unwrap();
+    assert_eq!(NaiveDateTime::decode(&val)?, expected);
+    assert_eq!(chrono::DateTime::<Utc>::decode(&val)?, expected.and_utc());
+    assert_eq!(String::decode(&val)?, "2023-01-01T12:34:56.789");
+    Ok(())
+}
+
+#[test]
+fn test_decode_date_and_time() -> anyhow::Result<()> {
+    let val = ColumnData::Date(Some(Date::new(DAYS_2023_01_01)));
+    assert_eq!(
+        NaiveDate::decode(&val)?,
+        NaiveDate::from_ymd_opt(2023, 1, 1).unwrap()
+    );
+    assert_eq!(String::decode(&val)?, "2023-01-01");
+
+    let val = ColumnData::Time(Some(Time::new(INCREMENTS_12_34_56_789, 7)));
+    assert_eq!(
+        NaiveTime::decode(&val)?,
+        NaiveTime::from_hms_milli_opt(12, 34, 56, 789).unwrap()
+    );
+    assert_eq!(String::decode(&val)?, "12:34:56.789");
+    Ok(())
+}
+
+#[test]
+fn test_decode_duration() -> anyhow::Result<()> {
+    // 08:30:00.0000001, the smallest step at the default scale.
+    let val = ColumnData::Time(Some(Time::new(30_600 * 10_000_000 + 1, 7)));
+    assert_eq!(
+        std::time::Duration::decode(&val)?,
+        std::time::Duration::new(30_600, 100)
+    );
+    assert_eq!(
+        chrono::TimeDelta::decode(&val)?,
+        chrono::TimeDelta::new(30_600, 100).unwrap()
+    );
+
+    // TIME(3) counts milliseconds.
+    let val = ColumnData::Time(Some(Time::new(30_600_250, 3)));
+    assert_eq!(
+        std::time::Duration::decode(&val)?,
+        std::time::Duration::from_millis(30_600_250)
+    );
+
+    assert!(std::time::Duration::decode(&ColumnData::I32(Some(1))).is_err());
+    Ok(())
+}
+
+#[test]
+fn test_decode_datetimeoffset() -> anyhow::Result<()> {
+    // The server stores the UTC time together with the offset in minutes.
+    let val = ColumnData::DateTimeOffset(Some(DateTimeOffset::new(datetime2(), 480)));
+    let utc = NaiveDate::from_ymd_opt(2023, 1, 1)
+        .unwrap()
+        .and_hms_milli_opt(12, 34, 56, 789)
+        .unwrap()
+        .and_utc();
+
+    let dt = chrono::DateTime::<FixedOffset>::decode(&val)?;
+    assert_eq!(dt, utc);
+    assert_eq!(dt.offset(), &FixedOffset::east_opt(8 * 3600).unwrap());
+    assert_eq!(chrono::DateTime::<Utc>::decode(&val)?, utc);
+    assert_eq!(String::decode(&val)?, "2023-01-01T20:34:56.789+08:00");
+
+    assert!(NaiveDateTime::decode(&val).is_err());
+    Ok(())
+}
+
+#[allow(unused)]
+#[derive(Debug, Row_Ext)]
+struct Event {
+    #[row_ext(serde)]
+    created_at: chrono::DateTime<Utc>,
+    #[row_ext(serde)]
+    updated_at: chrono::NaiveDateTime,
+}
+
+#[test]
+fn test_serde_temporal_round_trip() -> anyhow::Result<()> {
+    let cells = cells(vec![
+        (
+            "created_at",
+            ColumnData::DateTimeOffset(Some(DateTimeOffset::new(datetime2(), -300))),
+        ),
+        (
+            "updated_at",
+            ColumnData::SmallDateTime(Some(SmallDateTime::new(44_925, 754))),
+        ),
+    ]);
+    let event = Event::from_cells(cells.iter().map(|(c, v)| (c, v)))?;
+    assert_eq!(
+        event.created_at.to_rfc3339(),
+        "2023-01-01T12:34:56.789+00:00"
+    );
+    assert_eq!(event.updated_at.to_string(), "2023-01-01 12:34:00");
+    Ok(())
+}
+
+#[cfg(feature = "time")]
+#[test]
+fn test_decode_time() -> anyhow::Result<()> {
+    use time::{Month, OffsetDateTime, PrimitiveDateTime, UtcOffset};
+
+    let date = time::Date::from_calendar_date(2023, Month::January, 1)?;
+    let naive = PrimitiveDateTime::new(date, time::Time::from_hms_milli(12, 34, 56, 789)?);
+
+    let dt = time::Date::decode(&ColumnData::Date(Some(Date::new(DAYS_2023_01_01))))?;
+    assert_eq!(dt, date);
+
+    let dt = PrimitiveDateTime::decode(&ColumnData::DateTime2(Some(datetime2())))?;
+    assert_eq!(dt, naive);
+
+    let dto = DateTimeOffset::new(datetime2(), 480);
+    let dt = OffsetDateTime::decode(&ColumnData::DateTimeOffset(Some(dto)))?;
+    assert_eq!(dt, naive.assume_utc());
+    assert_eq!(dt.offset(), UtcOffset::from_hms(8, 0, 0)?);
+
+    let val = ColumnData::Time(Some(Time::new(30_600 * 10_000_000 + 1, 7)));
+    assert_eq!(
+        time::Duration::decode(&val)?,
+        time::Duration::new(30_600, 100)
+    );
+    Ok(())
+}
+
+#[cfg(feature = "jiff")]
+#[test]
+fn test_decode_jiff() -> anyhow::Result<()> {
+    use jiff::{civil, Timestamp};
+
+    let dt = civil::DateTime::decode(&ColumnData::DateTime2(Some(datetime2())))?;
+    assert_eq!(dt, civil::datetime(2023, 1, 1, 12, 34, 56, 789_000_000));
+
+    let dto = DateTimeOffset::new(datetime2(), 480);
+    let ts = Timestamp::decode(&ColumnData::DateTimeOffset(Some(dto)))?;
+    assert_eq!(ts, "2023-01-01T12:34:56.789Z".parse()?);
+    Ok(())
+}
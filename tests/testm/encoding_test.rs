@@ -0,0 +1,58 @@
+use tiberius::ColumnData;
+use tiberius_row::Row_Ext;
+
+use crate::helpers::cells;
+
+#[allow(unused)]
+#[derive(Debug, Row_Ext)]
+struct LegacyCustomer {
+    #[row_ext(encoding = "gbk")]
+    name: String,
+    #[row_ext(encoding = "big5", trim)]
+    city: String,
+    #[row_ext(encoding = "shift_jis", lossy)]
+    note: Option<String>,
+}
+
+fn bytes(b: &[u8]) -> ColumnData<'static> {
+    ColumnData::Binary(Some(b.to_vec().into()))
+}
+
+#[test]
+fn test_encoding() -> anyhow::Result<()> {
+    let cells = cells(vec![
+        ("name", bytes(&[0xd5, 0xc5, 0xc8, 0xfd])),
+        ("city", bytes(&[0xa5, 0x78, 0xa5, 0x5f, 0x20])),
+        ("note", bytes(&[0x82, 0xa0, 0x82])),
+    ]);
+    let customer = LegacyCustomer::from_cells(cells.iter().map(|(c, v)| (c, v)))?;
+    assert_eq!(customer.name, "张三");
+    assert_eq!(customer.city, "台北");
+    assert_eq!(customer.note.as_deref(), Some("あ\u{fffd}"));
+    Ok(())
+}
+
+#[test]
+fn test_encoding_null_and_text() -> anyhow::Result<()> {
+    let cells = cells(vec![
+        ("name", ColumnData::String(Some("李四".into()))),
+        ("city", ColumnData::Binary(None)),
+        ("note", ColumnData::Binary(None)),
+    ]);
+    let customer = LegacyCustomer::from_cells(cells.iter().map(|(c, v)| (c, v)))?;
+    assert_eq!(customer.name, "李四");
+    assert_eq!(customer.city, "");
+    assert_eq!(customer.note, None);
+    Ok(())
+}
+
+#[test]
+fn test_encoding_strict_error() {
+    let cells = cells(vec![
+        ("name", bytes(&[0xd5, 0xc5, 0x81])),
+        ("city", ColumnData::Binary(None)),
+    ]);
+    let err = LegacyCustomer::from_cells(cells.iter().map(|(c, v)| (c, v))).unwrap_err();
+    assert_eq!(err.to_string(), "failed to decode column `name`");
+    assert_eq!(err.root_cause().to_string(), "invalid GBK byte sequence");
+}
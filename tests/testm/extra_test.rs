@@ -0,0 +1,66 @@
+use std::collections::{BTreeMap, HashMap};
+
+use serde_json::json;
+use tiberius::ColumnData;
+use tiberius_row::{Row_Ext, SqlValue};
+
+use crate::helpers::cells;
+
+#[allow(unused)]
+#[derive(Debug, Row_Ext)]
+struct Order {
+    id: i32,
+    #[row_ext(rename = "CustomerName")]
+    customer: String,
+    #[row_ext(extra)]
+    rest: HashMap<String, SqlValue>,
+}
+
+#[allow(unused)]
+#[derive(Debug, Row_Ext)]
+struct OrderJson {
+    id: i32,
+    #[row_ext(extra)]
+    rest: serde_json::Map<String, serde_json::Value>,
+}
+
+#[allow(unused)]
+#[derive(Debug, Row_Ext)]
+struct OrderTotals {
+    id: i32,
+    #[row_ext(extra)]
+    totals: BTreeMap<String, Option<f64>>,
+}
+
+fn order_cells() -> Vec<(tiberius::Column, ColumnData<'static>)> {
+    cells(vec![
+        ("id", ColumnData::I32(Some(1))),
+        ("CustomerName", ColumnData::String(Some("Alice".into()))),
+        ("Total", ColumnData::F64(Some(9.5))),
+        ("Discount", ColumnData::F64(None)),
+    ])
+}
+
+#[test]
+fn test_extra() -> anyhow::Result<()> {
+    let cells = order_cells();
+    let order = Order::from_cells(cells.iter().map(|(c, v)| (c, v)))?;
+    assert_eq!(order.customer, "Alice");
+    assert_eq!(order.rest.len(), 2);
+    assert!(matches!(order.rest["Total"], ColumnData::F64(Some(v)) if v == 9.5));
+    assert!(matches!(order.rest["Discount"], ColumnData::F64(None)));
+
+    let order = OrderJson::from_cells(cells.iter().map(|(c, v)| (c, v)))?;
+    assert_eq!(
+        serde_json::Value::Object(order.rest),
+        json!({"CustomerName": "Alice", "Total": 9.5, "Discount": null})
+    );
+    Ok(())
+}
+
+#[test]
+fn test_extra_error() {
+    let cells = order_cells();
+    let err = OrderTotals::from_cells(cells.iter().map(|(c, v)| (c, v))).unwrap_err();
+    assert_eq!(err.to_string(), "failed to decode column `CustomerName`");
+}
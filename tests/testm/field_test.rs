@@ -0,0 +1,50 @@
+use serde::{Deserialize, Serialize};
+use tiberius::ColumnData;
+use tiberius_row::{Field, Row_Ext};
+
+use crate::helpers::cells;
+
+#[derive(Debug, Row_Ext, Serialize, Deserialize)]
+struct CustomerPatch {
+    id: i32,
+    #[serde(default, skip_serializing_if = "Field::is_absent")]
+    name: Field<String>,
+    #[serde(default, skip_serializing_if = "Field::is_absent")]
+    email: Field<String>,
+    #[serde(default, skip_serializing_if = "Field::is_absent")]
+    age: Field<i32>,
+}
+
+#[test]
+fn test_field() -> anyhow::Result<()> {
+    let cells = cells(vec![
+        ("id", ColumnData::I32(Some(1))),
+        ("name", ColumnData::String(Some("Alice".into()))),
+        ("age", ColumnData::I32(None)),
+    ]);
+    let patch = CustomerPatch::from_cells(cells.iter().map(|(c, v)| (c, v)))?;
+    assert_eq!(patch.name, Field::Value("Alice".to_string()));
+    assert_eq!(patch.email, Field::Absent);
+    assert_eq!(patch.age, Field::Null);
+    assert_eq!(patch.name.value().map(String::as_str), Some("Alice"));
+    assert_eq!(patch.age.into_value(), None);
+    Ok(())
+}
+
+#[test]
+fn test_field_serde() -> anyhow::Result<()> {
+    let patch = CustomerPatch {
+        id: 1,
+        name: Field::Value("Alice".to_string()),
+        email: Field::Absent,
+        age: Field::Null,
+    };
+    let json = serde_json::to_string(&patch)?;
+    assert_eq!(json, r#"{"id":1,"name":"Alice","age":null}"#);
+
+    let patch: CustomerPatch = serde_json::from_str(&json)?;
+    assert_eq!(patch.name, Field::Value("Alice".to_string()));
+    assert_eq!(patch.email, Field::Absent);
+    assert_eq!(patch.age, Field::Null);
+    Ok(())
+}
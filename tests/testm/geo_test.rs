@@ -0,0 +1,111 @@
+use geo_types::{point, polygon, Geometry, MultiPoint, Point, Polygon};
+use tiberius::ColumnData;
+use tiberius_row::{Row_Ext, Spatial};
+
+use crate::helpers::cells;
+
+#[allow(unused)]
+#[derive(Debug, Row_Ext)]
+struct Store {
+    #[row_ext(wkt)]
+    location: Point<f64>,
+    #[row_ext(wkt)]
+    area: Spatial<Polygon<f64>>,
+    #[row_ext(wkb)]
+    entrance: Spatial<Point<f64>>,
+    #[row_ext(wkb)]
+    shape: Geometry<f64>,
+    #[row_ext(wkt)]
+    parking: Option<Point<f64>>,
+}
+
+fn text(s: &str) -> ColumnData<'static> {
+    ColumnData::String(Some(s.to_string().into()))
+}
+
+fn bytes(b: Vec<u8>) -> ColumnData<'static> {
+    ColumnData::Binary(Some(b.into()))
+}
+
+/// Little-endian WKB of a point, with the EWKB SRID when given.
+fn wkb_point(x: f64, y: f64, srid: Option<u32>) -> Vec<u8> {
+    let mut wkb = vec![1];
+    match srid {
+        Some(srid) => {
+            wkb.extend(0x2000_0001u32.to_le_bytes());
+            wkb.extend(srid.to_le_bytes());
+        }
+        None => wkb.extend(1u32.to_le_bytes()),
+    }
+    wkb.extend(x.to_le_bytes());
+    wkb.extend(y.to_le_bytes());
+    wkb
+}
+
+#[test]
+fn test_geo() -> anyhow::Result<()> {
+    // A big-endian multipoint holding one little-endian and one big-endian point.
+    let mut multipoint = vec![0];
+    multipoint.extend(4u32.to_be_bytes());
+    multipoint.extend(2u32.to_be_bytes());
+    multipoint.extend(wkb_point(1.0, 2.0, None));
+    multipoint.push(0);
+    multipoint.extend(1u32.to_be_bytes());
+    multipoint.extend(3f64.to_be_bytes());
+    multipoint.extend(4f64.to_be_bytes());
+
+    let cells = cells(vec![
+        ("location", text("POINT (121.47 31.23)")),
+        (
+            "area",
+            text("SRID=4326;POLYGON ((0 0, 1 0, 1 1, 0 1, 0 0))"),
+        ),
+        ("entrance", bytes(wkb_point(121.5, 31.2, Some(4326)))),
+        ("shape", bytes(multipoint)),
+        ("parking", ColumnData::String(None)),
+    ]);
+    let store = Store::from_cells(cells.iter().map(|(c, v)| (c, v)))?;
+    assert_eq!(store.location, point!(x: 121.47, y: 31.23));
+    assert_eq!(
+        store.area.geometry,
+        polygon![(x: 0., y: 0.), (x: 1., y: 0.), (x: 1., y: 1.), (x: 0., y: 1.), (x: 0., y: 0.)]
+    );
+    assert_eq!(store.area.srid, Some(4326));
+    assert_eq!(store.entrance.geometry, point!(x: 121.5, y: 31.2));
+    assert_eq!(store.entrance.srid, Some(4326));
+    assert_eq!(
+        store.shape,
+        Geometry::MultiPoint(MultiPoint(vec![point!(x: 1., y: 2.), point!(x: 3., y: 4.)]))
+    );
+    assert_eq!(store.parking, None);
+    assert_eq!(store.area.to_wkt(), "POLYGON((0 0,1 0,1 1,0 1,0 0))");
+    Ok(())
+}
+
+#[test]
+fn test_geo_mismatch() {
+    let cells = cells(vec![("location", text("LINESTRING (0 0, 1 1)"))]);
+    let err = Store::from_cells(cells.iter().map(|(c, v)| (c, v))).unwrap_err();
+    assert_eq!(err.to_string(), "failed to decode column `location`");
+    assert_eq!(
+        err.root_cause().to_string(),
+        "cannot decode LINESTRING as `Point`"
+    );
+}
+
+#[test]
+fn test_wkb_error() {
+    let mut wkb = wkb_point(1.0, 2.0, None);
+    wkb.truncate(12);
+    let cells = cells(vec![
+        ("location", text("POINT (0 0)")),
+        ("area", text("POLYGON ((0 0, 1 0, 1 1, 0 0))")),
+        ("entrance", bytes(wkb)),
+    ]);
+    let err = Store::from_cells(cells.iter().map(|(c, v)| (c, v))).unwrap_err();
+    assert_eq!(err.to_string(), "failed to decode column `entrance`");
+    assert_eq!(
+        err.root_cause().to_string(),
+        "invalid WKB: unexpected end of data"
+    );
+}
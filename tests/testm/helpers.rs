@@ -159,7 +159,7 @@ async fn test_create_table() -> anyhow::Result<()> {
     let query = Query::new("SELECT * FROM test");
     let rows = query.query(&mut client).await?.into_first_result().await?;
     // println!("{:?}", rows);
-    assert!(rows.len() > 0);
+    assert!(!rows.is_empty());
     for r in rows {
         let cols = r
             .columns()
@@ -172,3 +172,19 @@ async fn test_create_table() -> anyhow::Result<()> {
     }
     Ok(())
 }
+
+/// Builds the `(Column, ColumnData)` pairs that `from_cells` decodes, standing
+/// in for a row fetched from the server.
+pub fn cells(
+    values: Vec<(&str, tiberius::ColumnData<'static>)>,
+) -> Vec<(tiberius::Column, tiberius::ColumnData<'static>)> {
+    values
+        .into_iter()
+        .map(|(name, val)| {
+            (
+                tiberius::Column::new(name.to_string(), tiberius::ColumnType::Null),
+                val,
+            )
+        })
+        .collect()
+}
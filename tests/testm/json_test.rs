@@ -0,0 +1,103 @@
+use serde::Deserialize;
+use tiberius::ColumnData;
+use tiberius_row::Row_Ext;
+
+use crate::helpers::cells;
+
+#[derive(Debug, PartialEq, Deserialize)]
+struct Item {
+    sku: String,
+    qty: i32,
+}
+
+#[allow(unused)]
+#[derive(Debug, Row_Ext)]
+struct Order {
+    id: i32,
+    #[row_ext(json)]
+    items: Vec<Item>,
+    #[row_ext(json)]
+    payload: serde_json::Value,
+    #[row_ext(rename = "payload", json_path = "$.customer.name")]
+    customer: String,
+    #[row_ext(rename = "payload", json_path = "$.tags[1]")]
+    second_tag: Option<String>,
+    #[row_ext(rename = "payload", json_path = "$['customer'].vip")]
+    vip: Option<bool>,
+    #[row_ext(json)]
+    notes: Option<Vec<String>>,
+}
+
+fn order_cells(payload: &str) -> Vec<(tiberius::Column, ColumnData<'static>)> {
+    cells(vec![
+        ("id", ColumnData::I32(Some(1))),
+        (
+            "items",
+            ColumnData::String(Some(
+                r#"[{"sku":"A-1","qty":2},{"sku":"B-7","qty":1}]"#.into(),
+            )),
+        ),
+        (
+            "payload",
+            ColumnData::String(Some(payload.to_string().into())),
+        ),
+        ("notes", ColumnData::String(None)),
+    ])
+}
+
+#[test]
+fn test_json() -> anyhow::Result<()> {
+    let cells = order_cells(r#"{"customer":{"name":"alice"},"tags":["new","gift"]}"#);
+    let order = Order::from_cells(cells.iter().map(|(c, v)| (c, v)))?;
+    assert_eq!(
+        order.items,
+        vec![
+            Item {
+                sku: "A-1".to_string(),
+                qty: 2
+            },
+            Item {
+                sku: "B-7".to_string(),
+                qty: 1
+            },
+        ]
+    );
+    assert_eq!(order.payload["tags"][0], "new");
+    assert_eq!(order.customer, "alice");
+    assert_eq!(order.second_tag.as_deref(), Some("gift"));
+    assert_eq!(order.vip, None);
+    assert_eq!(order.notes, None);
+    Ok(())
+}
+
+#[test]
+fn test_json_errors() {
+    let cells = order_cells(r#"{"tags":[]}"#);
+    let err = Order::from_cells(cells.iter().map(|(c, v)| (c, v))).unwrap_err();
+    assert_eq!(err.to_string(), "failed to decode column `payload`");
+    assert_eq!(
+        err.root_cause().to_string(),
+        "invalid type: null, expected a string"
+    );
+
+    let cells = order_cells("{not json");
+    let err = Order::from_cells(cells.iter().map(|(c, v)| (c, v))).unwrap_err();
+    assert_eq!(err.to_string(), "failed to decode column `payload`");
+}
+
+#[allow(unused)]
+#[derive(Debug, Row_Ext)]
+struct BadPath {
+    #[row_ext(json_path = "customer.name")]
+    payload: String,
+}
+
+#[test]
+fn test_json_invalid_path() {
+    let cells = cells(vec![("payload", ColumnData::String(Some("{}".into())))]);
+    let err = BadPath::from_cells(cells.iter().map(|(c, v)| (c, v))).unwrap_err();
+    assert_eq!(
+        err.root_cause().to_string(),
+        "invalid json_path `customer.name`"
+    );
+}
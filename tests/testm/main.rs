@@ -1,2 +1,28 @@
+mod borrow_test;
+#[cfg(feature = "compress")]
+mod compress_test;
+mod convert_test;
+mod de_test;
+mod decode_test;
+#[cfg(feature = "encoding")]
+mod encoding_test;
+mod extra_test;
+mod field_test;
+#[cfg(feature = "geo")]
+mod geo_test;
 mod helpers;
+mod json_test;
+mod mapper_test;
+mod normalize_test;
+mod params_test;
+mod positional_test;
+mod procedure_test;
+mod query_test;
+mod results_test;
 mod row_ext_test;
+mod split_test;
+#[cfg(feature = "chrono-tz")]
+mod tz_test;
+mod update_test;
+#[cfg(feature = "xml")]
+mod xml_test;
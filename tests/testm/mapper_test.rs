@@ -0,0 +1,121 @@
+use std::collections::HashMap;
+
+use tiberius::{Column, ColumnData, ColumnType};
+use tiberius_row::{RowMapper, Row_Ext, SqlValue};
+
+#[allow(unused)]
+#[derive(Debug, Row_Ext)]
+struct Product {
+    id: i32,
+    #[row_ext(rename = "ProductName")]
+    name: String,
+    price: Option<f64>,
+    #[row_ext(extra)]
+    rest: HashMap<String, SqlValue>,
+}
+
+fn columns() -> Vec<Column> {
+    vec![
+        Column::new("price".to_string(), ColumnType::Floatn),
+        Column::new("id".to_string(), ColumnType::Int4),
+        Column::new("ProductName".to_string(), ColumnType::NVarchar),
+        Column::new("stock".to_string(), ColumnType::Int4),
+    ]
+}
+
+fn row<'a>(
+    columns: &'a [Column],
+    values: Vec<ColumnData<'static>>,
+) -> Vec<(&'a Column, ColumnData<'static>)> {
+    columns.iter().zip(values).collect()
+}
+
+#[test]
+fn test_row_mapper() -> anyhow::Result<()> {
+    let columns = columns();
+    let mapper = RowMapper::<Product>::new(&columns)?;
+    let rows = [
+        row(
+            &columns,
+            vec![
+                ColumnData::F64(Some(9.5)),
+                ColumnData::I32(Some(1)),
+                ColumnData::String(Some("Pen".into())),
+                ColumnData::I32(Some(100)),
+            ],
+        ),
+        row(
+            &columns,
+            vec![
+                ColumnData::F64(None),
+                ColumnData::I32(Some(2)),
+                ColumnData::String(Some("Ink".into())),
+                ColumnData::I32(Some(0)),
+            ],
+        ),
+    ];
+    let products = rows
+        .iter()
+        .map(|row| mapper.decode_cells(row.iter().map(|(c, v)| (*c, v))))
+        .collect::<anyhow::Result<Vec<_>>>()?;
+    assert_eq!(products[0].id, 1);
+    assert_eq!(products[0].name, "Pen");
+    assert_eq!(products[0].price, Some(9.5));
+    assert!(matches!(
+        products[0].rest["stock"],
+        ColumnData::I32(Some(100))
+    ));
+    assert_eq!(products[1].name, "Ink");
+    assert_eq!(products[1].price, None);
+    Ok(())
+}
+
+#[test]
+fn test_row_mapper_type_check() {
+    let mut columns = columns();
+    columns[1] = Column::new("id".to_string(), ColumnType::NVarchar);
+    let err = RowMapper::<Product>::new(&columns).err().unwrap();
+    assert_eq!(
+        err.to_string(),
+        "column `id` of type NVarchar cannot be decoded into field `id`"
+    );
+}
+
+#[test]
+fn test_row_mapper_column_count() -> anyhow::Result<()> {
+    let columns = columns();
+    let mapper = RowMapper::<Product>::new(&columns)?;
+    let values = [ColumnData::F64(None), ColumnData::I32(Some(1))];
+    let err = mapper
+        .decode_cells(columns.iter().zip(&values))
+        .err()
+        .unwrap();
+    assert_eq!(
+        err.to_string(),
+        "row has 2 columns, but the mapper was built for 4"
+    );
+    Ok(())
+}
+
+#[derive(Debug, Row_Ext)]
+struct Keyed<K: tiberius_row::SqlDecode> {
+    id: K,
+    #[row_ext(rename = "ProductName", trim)]
+    name: String,
+}
+
+#[test]
+fn test_row_mapper_generic() -> anyhow::Result<()> {
+    let columns = columns();
+    let mapper = RowMapper::<Keyed<i64>>::new(&columns)?;
+    let values = [
+        ColumnData::F64(None),
+        ColumnData::I32(Some(7)),
+        ColumnData::String(Some(" Pen ".into())),
+        ColumnData::I32(Some(0)),
+    ];
+    let keyed = mapper.decode_cells(columns.iter().zip(&values))?;
+    assert_eq!(keyed.id, 7);
+    assert_eq!(keyed.name, "Pen");
+    Ok(())
+}
@@ -0,0 +1,79 @@
+use tiberius::ColumnData;
+use tiberius_row::Row_Ext;
+
+use crate::helpers::cells;
+
+#[allow(unused)]
+#[derive(Debug, Row_Ext)]
+struct Customer {
+    #[row_ext(trim)]
+    code: String,
+    #[row_ext(trim = "right")]
+    name: String,
+    #[row_ext(trim, empty_as_none)]
+    email: Option<String>,
+    #[row_ext(bool_from = ["Y", "N"])]
+    active: bool,
+    #[row_ext(trim, bool_from = ["Y", "N"])]
+    vip: Option<bool>,
+    #[row_ext(empty_as_none)]
+    level: Option<i32>,
+}
+
+fn text(s: &str) -> ColumnData<'static> {
+    ColumnData::String(Some(s.to_string().into()))
+}
+
+#[test]
+fn test_normalize() -> anyhow::Result<()> {
+    let cells = cells(vec![
+        ("code", text("  C001      ")),
+        ("name", text("  Alice     ")),
+        ("email", text("    ")),
+        ("active", text("Y")),
+        ("vip", text("N ")),
+        ("level", ColumnData::I32(Some(3))),
+    ]);
+    let customer = Customer::from_cells(cells.iter().map(|(c, v)| (c, v)))?;
+    assert_eq!(customer.code, "C001");
+    assert_eq!(customer.name, "  Alice");
+    assert_eq!(customer.email, None);
+    assert!(customer.active);
+    assert_eq!(customer.vip, Some(false));
+    assert_eq!(customer.level, Some(3));
+    Ok(())
+}
+
+#[test]
+fn test_normalize_null() -> anyhow::Result<()> {
+    let cells = cells(vec![
+        ("code", text("C002")),
+        ("name", ColumnData::String(None)),
+        ("email", text("bob@example.com")),
+        ("active", text("N")),
+        ("vip", ColumnData::String(None)),
+        ("level", ColumnData::I32(None)),
+    ]);
+    let customer = Customer::from_cells(cells.iter().map(|(c, v)| (c, v)))?;
+    assert_eq!(customer.name, "");
+    assert_eq!(customer.email.as_deref(), Some("bob@example.com"));
+    assert!(!customer.active);
+    assert_eq!(customer.vip, None);
+    assert_eq!(customer.level, None);
+    Ok(())
+}
+
+#[test]
+fn test_bool_from_error() {
+    let cells = cells(vec![
+        ("code", text("C003")),
+        ("name", text("Carol")),
+        ("email", ColumnData::String(None)),
+        ("active", text("yes")),
+        ("vip", ColumnData::String(None)),
+        ("level", ColumnData::I32(None)),
+    ]);
+    let err = Customer::from_cells(cells.iter().map(|(c, v)| (c, v))).unwrap_err();
+    assert_eq!(err.to_string(), "failed to decode column `active`");
+    assert_eq!(err.root_cause().to_string(), "`yes` is neither `Y` nor `N`");
+}
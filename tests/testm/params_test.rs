@@ -0,0 +1,280 @@
+use std::collections::HashMap;
+
+use chrono::{NaiveDate, NaiveDateTime};
+use serde::{Deserialize, Serialize};
+use tiberius::numeric::Decimal;
+use tiberius::{ColumnData, Uuid};
+use tiberius_row::{Field, Row_Ext, SqlValue, ToParams};
+
+#[derive(Debug, Serialize, Deserialize)]
+struct Tag {
+    name: String,
+}
+
+#[allow(unused)]
+#[derive(Debug, Row_Ext, ToParams)]
+struct Invoice {
+    id: Uuid,
+    #[row_ext(rename = "CustomerName", trim)]
+    customer: String,
+    total: Decimal,
+    issued_at: NaiveDateTime,
+    #[row_ext(rename = "Quantity")]
+    quantity: Option<i32>,
+    #[row_ext(json)]
+    tags: Vec<Tag>,
+    #[row_ext(json)]
+    meta: Option<Tag>,
+    #[row_ext(extra)]
+    rest: HashMap<String, SqlValue>,
+}
+
+fn invoice() -> Invoice {
+    Invoice {
+        id: Uuid::nil(),
+        customer: "Alice".to_string(),
+        total: Decimal::new(1250, 2),
+        issued_at: NaiveDate::from_ymd_opt(2024, 5, 1)
+            .unwrap()
+            .and_hms_opt(8, 30, 0)
+            .unwrap(),
+        quantity: None,
+        tags: vec![Tag {
+            name: "vip".to_string(),
+        }],
+        meta: None,
+        rest: HashMap::new(),
+    }
+}
+
+#[test]
+fn test_to_params() -> anyhow::Result<()> {
+    let params = invoice().to_params()?;
+    assert_eq!(
+        params.columns(),
+        [
+            "id",
+            "CustomerName",
+            "total",
+            "issued_at",
+            "Quantity",
+            "tags",
+            "meta"
+        ]
+    );
+    let values = params.values().collect::<Vec<_>>();
+    assert!(matches!(values[0], ColumnData::Guid(Some(_))));
+    assert!(matches!(values[1], ColumnData::String(Some(v)) if v == "Alice"));
+    assert!(
+        matches!(values[2], ColumnData::Numeric(Some(v)) if v.value() == 1250 && v.scale() == 2)
+    );
+    assert!(matches!(values[3], ColumnData::DateTime2(Some(_))));
+    assert!(matches!(values[4], ColumnData::I32(None)));
+    assert!(matches!(values[5], ColumnData::String(Some(v)) if v == r#"[{"name":"vip"}]"#));
+    assert!(matches!(values[6], ColumnData::String(None)));
+    assert_eq!(params.as_refs().len(), 7);
+    Ok(())
+}
+
+#[test]
+fn test_params_sql() -> anyhow::Result<()> {
+    let params = invoice().to_params()?;
+    assert_eq!(
+        params.insert_sql("dbo.Invoices"),
+        "INSERT INTO dbo.Invoices ([id], [CustomerName], [total], [issued_at], [Quantity], [tags], [meta]) \
+         VALUES (@P1, @P2, @P3, @P4, @P5, @P6, @P7)"
+    );
+    assert_eq!(
+        params.update_sql("dbo.Invoices", &["id"])?,
+        "UPDATE dbo.Invoices SET [CustomerName] = @P2, [total] = @P3, [issued_at] = @P4, \
+         [Quantity] = @P5, [tags] = @P6, [meta] = @P7 WHERE [id] = @P1"
+    );
+    Ok(())
+}
+
+#[derive(Serialize, ToParams)]
+struct AuditEntry<'a> {
+    #[serde(rename = "User")]
+    user: &'a str,
+    #[serde(skip)]
+    #[allow(unused)]
+    session: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    detail: Option<String>,
+}
+
+#[test]
+fn test_to_params_serde_attrs() -> anyhow::Result<()> {
+    let entry = AuditEntry {
+        user: "bob",
+        session: 42,
+        detail: Some("login".to_string()),
+    };
+    let params = entry.to_params()?;
+    assert_eq!(params.columns(), ["User", "detail"]);
+    let values = params.values().collect::<Vec<_>>();
+    assert!(matches!(values[0], ColumnData::String(Some(v)) if v == "bob"));
+    assert!(matches!(values[1], ColumnData::String(Some(v)) if v == "login"));
+    Ok(())
+}
+
+#[test]
+fn test_update_sql_all_keys() -> anyhow::Result<()> {
+    let entry = AuditEntry {
+        user: "bob",
+        session: 42,
+        detail: None,
+    };
+    let err = entry
+        .to_params()?
+        .update_sql("dbo.Audit", &["User", "detail"])
+        .unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "no columns to update in dbo.Audit, all are keys"
+    );
+    Ok(())
+}
+
+#[derive(Row_Ext, ToParams)]
+struct CustomerPatch {
+    id: i32,
+    name: Field<String>,
+    email: Field<String>,
+    #[row_ext(bool_from = ["Y", "N"])]
+    active: Field<bool>,
+}
+
+#[test]
+fn test_to_params_absent_fields() -> anyhow::Result<()> {
+    let patch = CustomerPatch {
+        id: 7,
+        name: Field::Value("Alice".to_string()),
+        email: Field::Null,
+        active: Field::Absent,
+    };
+    let params = patch.to_params()?;
+    assert_eq!(params.columns(), ["id", "name", "email"]);
+    let values = params.values().collect::<Vec<_>>();
+    assert!(matches!(values[1], ColumnData::String(Some(v)) if v == "Alice"));
+    assert!(matches!(values[2], ColumnData::String(None)));
+    assert_eq!(
+        params.insert_sql("dbo.Customers"),
+        "INSERT INTO dbo.Customers ([id], [name], [email]) VALUES (@P1, @P2, @P3)"
+    );
+    assert_eq!(
+        params.update_sql("dbo.Customers", &["id"])?,
+        "UPDATE dbo.Customers SET [name] = @P2, [email] = @P3 WHERE [id] = @P1"
+    );
+
+    let patch = CustomerPatch {
+        id: 7,
+        name: Field::Absent,
+        email: Field::Absent,
+        active: Field::Value(false),
+    };
+    let params = patch.to_params()?;
+    assert_eq!(
+        params.update_sql("dbo.Customers", &["id"])?,
+        "UPDATE dbo.Customers SET [active] = @P2 WHERE [id] = @P1"
+    );
+    let values = params.values().collect::<Vec<_>>();
+    assert!(matches!(values[1], ColumnData::String(Some(v)) if v == "N"));
+    Ok(())
+}
+
+/// Decodes `params` back through `Row_Ext`, as if read from the columns they
+/// were written to.
+fn read_back<T: tiberius_row::FromRow>(params: &tiberius_row::Params) -> anyhow::Result<T> {
+    let cells = params
+        .columns()
+        .iter()
+        .zip(params.values())
+        .map(|(&column, value)| (column, value.clone()))
+        .collect();
+    let cells = crate::helpers::cells(cells);
+    T::from_cells(cells.iter().map(|(c, v)| (c, v)))
+}
+
+#[derive(Debug, PartialEq, Serialize, Row_Ext, ToParams)]
+#[serde(rename_all = "PascalCase")]
+struct Host {
+    host_name: String,
+    #[row_ext(bool_from = ["Y", "N"])]
+    enabled: bool,
+    #[row_ext(bool_from = ["Y", "N"])]
+    primary: Option<bool>,
+    #[row_ext(from_str)]
+    address: std::net::IpAddr,
+    #[row_ext(split = ",")]
+    ports: Vec<u16>,
+}
+
+#[test]
+fn test_to_params_reverses_row_ext() -> anyhow::Result<()> {
+    let host = Host {
+        host_name: "db1".to_string(),
+        enabled: true,
+        primary: None,
+        address: "10.0.0.1".parse()?,
+        ports: vec![1433, 1434],
+    };
+    let params = host.to_params()?;
+    assert_eq!(
+        params.columns(),
+        ["HostName", "Enabled", "Primary", "Address", "Ports"]
+    );
+    let values = params.values().collect::<Vec<_>>();
+    assert!(matches!(values[1], ColumnData::String(Some(v)) if v == "Y"));
+    assert!(matches!(values[2], ColumnData::String(None)));
+    assert!(matches!(values[3], ColumnData::String(Some(v)) if v == "10.0.0.1"));
+    assert!(matches!(values[4], ColumnData::String(Some(v)) if v == "1433,1434"));
+    assert_eq!(read_back::<Host>(&params)?, host);
+    Ok(())
+}
+
+#[cfg(all(feature = "compress", feature = "encoding"))]
+#[derive(Debug, PartialEq, Row_Ext, ToParams)]
+struct Archive {
+    #[row_ext(compressed)]
+    blob: Vec<u8>,
+    #[row_ext(compressed = "utf-16")]
+    body: Option<String>,
+    #[row_ext(encoding = "gbk")]
+    title: String,
+    #[row_ext(compressed, encoding = "gbk")]
+    note: String,
+}
+
+#[cfg(all(feature = "compress", feature = "encoding"))]
+#[test]
+fn test_to_params_compressed_encoded() -> anyhow::Result<()> {
+    let archive = Archive {
+        blob: vec![1, 2, 3],
+        body: Some("正文".to_string()),
+        title: "标题".to_string(),
+        note: "备注".to_string(),
+    };
+    let params = archive.to_params()?;
+    let values = params.values().collect::<Vec<_>>();
+    assert!(matches!(values[2], ColumnData::Binary(Some(v)) if v[..] == [0xb1, 0xea, 0xcc, 0xe2]));
+    assert_eq!(read_back::<Archive>(&params)?, archive);
+    Ok(())
+}
+
+#[cfg(feature = "encoding")]
+#[derive(ToParams)]
+struct Label {
+    #[row_ext(encoding = "latin1")]
+    text: String,
+}
+
+#[cfg(feature = "encoding")]
+#[test]
+fn test_to_params_unencodable() {
+    let label = Label {
+        text: "标题".to_string(),
+    };
+    let err = label.to_params().unwrap_err();
+    assert_eq!(err.to_string(), "`标题` cannot be encoded in windows-1252");
+}
@@ -0,0 +1,76 @@
+use tiberius::{Column, ColumnData, ColumnType};
+use tiberius_row::{FromRow, RowMapper};
+
+use crate::helpers::cells;
+
+#[test]
+fn test_tuple_from_cells() -> anyhow::Result<()> {
+    let cells = cells(vec![
+        ("id", ColumnData::I32(Some(1))),
+        ("name", ColumnData::String(Some("Alice".into()))),
+        ("email", ColumnData::String(None)),
+        ("ignored", ColumnData::Bit(Some(true))),
+    ]);
+    let row = cells.iter().map(|(c, v)| (c, v));
+    let (id, name, email) = <(i64, String, Option<String>)>::from_cells(row)?;
+    assert_eq!(id, 1);
+    assert_eq!(name, "Alice");
+    assert_eq!(email, None);
+    Ok(())
+}
+
+#[test]
+fn test_scalar_from_cells() -> anyhow::Result<()> {
+    let cells = cells(vec![
+        ("count", ColumnData::I64(Some(42))),
+        ("other", ColumnData::I64(None)),
+    ]);
+    assert_eq!(i64::from_cells(cells.iter().map(|(c, v)| (c, v)))?, 42);
+    let null = cells[1..].iter().map(|(c, v)| (c, v));
+    assert_eq!(Option::<i64>::from_cells(null)?, None);
+    Ok(())
+}
+
+#[test]
+fn test_positional_errors() {
+    let cells = cells(vec![("id", ColumnData::String(Some("x".into())))]);
+    let err = <(i32, String)>::from_cells(cells.iter().map(|(c, v)| (c, v)))
+        .err()
+        .unwrap();
+    assert_eq!(err.to_string(), "failed to decode column `id`");
+    let cells = cells.iter().map(|(c, v)| (c, v));
+    let err = <(String, i32)>::from_cells(cells).err().unwrap();
+    assert_eq!(err.to_string(), "row has no column 1");
+    let empty = Vec::<(Column, ColumnData<'static>)>::new();
+    let (a, b) =
+        <(Option<i32>, Option<String>)>::from_cells(empty.iter().map(|(c, v)| (c, v))).unwrap();
+    assert_eq!((a, b), (None, None));
+}
+
+#[test]
+fn test_tuple_row_mapper() -> anyhow::Result<()> {
+    let columns = vec![
+        Column::new("id".to_string(), ColumnType::Int4),
+        Column::new("name".to_string(), ColumnType::NVarchar),
+    ];
+    let mapper = RowMapper::<(i32, String)>::new(&columns)?;
+    let values = [
+        ColumnData::I32(Some(7)),
+        ColumnData::String(Some("Bob".into())),
+    ];
+    assert_eq!(
+        mapper.decode_cells(columns.iter().zip(&values))?,
+        (7, "Bob".to_string())
+    );
+    let err = RowMapper::<(String, String)>::new(&columns).err().unwrap();
+    assert_eq!(
+        err.to_string(),
+        "column `id` of type Int4 cannot be decoded into field `0`"
+    );
+    let names = RowMapper::<String>::new(&columns[1..])?;
+    assert_eq!(
+        names.decode_cells(columns[1..].iter().zip(&values[1..]))?,
+        "Bob"
+    );
+    Ok(())
+}
@@ -0,0 +1,118 @@
+use tiberius::ColumnData;
+use tiberius_db_tester::DBTester;
+use tiberius_row::{Procedure, Row_Ext};
+
+#[allow(unused)]
+#[derive(Debug, Row_Ext)]
+struct Sample {
+    id: i32,
+    nvarchar_col: Option<String>,
+}
+
+#[derive(Debug, Procedure)]
+#[proc(name = "dbo.row_ext_samples", results = "(Vec<Sample>, Option<i32>)")]
+struct Samples {
+    min_id: i32,
+    #[proc(rename = "label")]
+    prefix: String,
+    #[proc(output)]
+    total: Option<i64>,
+    #[proc(output, sql_type = "nvarchar(100)")]
+    message: Option<String>,
+}
+
+#[derive(Debug, Procedure)]
+#[proc(name = "dbo.row_ext_touch")]
+struct Touch {
+    #[proc(output)]
+    touched: Option<bool>,
+}
+
+#[derive(Debug, Procedure)]
+#[proc(name = "dbo.row_ext_status")]
+struct Status {
+    #[proc(output)]
+    return_status: Option<i32>,
+    #[proc(output, sql_type = "decimal(10, 2)")]
+    amount: Option<tiberius::numeric::Decimal>,
+}
+
+#[test]
+fn test_procedure_sql() {
+    assert_eq!(
+        Samples::SQL,
+        "DECLARE @__return_status int, @__out_3 bigint = @P3, @__out_4 nvarchar(100) = @P4;\n\
+         EXEC @__return_status = dbo.row_ext_samples @min_id = @P1, @label = @P2, \
+         @total = @__out_3 OUTPUT, @message = @__out_4 OUTPUT;\n\
+         SELECT @__return_status AS [__return_status], @__out_3 AS [__out_3], \
+         @__out_4 AS [__out_4];"
+    );
+    assert_eq!(
+        Touch::SQL,
+        "DECLARE @__return_status int, @__out_1 bit = @P1;\n\
+         EXEC @__return_status = dbo.row_ext_touch @touched = @__out_1 OUTPUT;\n\
+         SELECT @__return_status AS [__return_status], @__out_1 AS [__out_1];"
+    );
+    assert_eq!(
+        Status::SQL,
+        "DECLARE @__return_status int, @__out_1 int = @P1, @__out_2 decimal(10, 2) = @P2;\n\
+         EXEC @__return_status = dbo.row_ext_status @return_status = @__out_1 OUTPUT, \
+         @amount = @__out_2 OUTPUT;\n\
+         SELECT @__return_status AS [__return_status], @__out_1 AS [__out_1], \
+         @__out_2 AS [__out_2];"
+    );
+}
+
+#[test]
+fn test_procedure_params() {
+    let samples = Samples {
+        min_id: 3,
+        prefix: "a".to_owned(),
+        total: None,
+        message: None,
+    };
+    let params = samples.params();
+    assert_eq!(params.columns(), ["min_id", "label", "total", "message"]);
+    let values = params.values().collect::<Vec<_>>();
+    assert!(matches!(values[0], ColumnData::I32(Some(3))));
+    assert!(matches!(values[2], ColumnData::I64(None)));
+}
+
+#[tokio::test]
+#[ignore = "needs the test database"]
+async fn test_exec_procedure() -> anyhow::Result<()> {
+    let test_app = DBTester::new("101.95.95.58", 1433, "sa", "Ibm123", "migrates/test.sql");
+    let mut client = test_app.get_client().await;
+    client
+        .simple_query(
+            "CREATE OR ALTER PROCEDURE dbo.row_ext_samples \
+                 @min_id int, @label nvarchar(100), @total bigint OUTPUT, \
+                 @message nvarchar(100) OUTPUT \
+             AS BEGIN \
+                 SELECT id, nvarchar_col FROM test WHERE id >= @min_id; \
+                 SELECT COUNT(*) FROM test; \
+                 SELECT @total = COUNT(*) FROM test WHERE id >= @min_id; \
+                 SET @message = @label + N' done'; \
+                 RETURN 7; \
+             END",
+        )
+        .await?
+        .into_results()
+        .await?;
+
+    let response = Samples {
+        min_id: 0,
+        prefix: "samples".to_owned(),
+        total: None,
+        message: None,
+    }
+    .exec(&mut client)
+    .await?;
+    let (rows, count) = response.results;
+    assert_eq!(rows.len(), 1);
+    assert_eq!(count, Some(1));
+    assert_eq!(response.params.total, Some(1));
+    assert_eq!(response.params.message.as_deref(), Some("samples done"));
+    assert_eq!(response.return_status, 7);
+    Ok(())
+}
@@ -0,0 +1,103 @@
+use futures_util::StreamExt as _;
+use tiberius_db_tester::DBTester;
+use tiberius_row::{
+    query_all, query_as, query_one, query_optional, query_stream, RowStream, Row_Ext,
+};
+
+#[allow(unused)]
+#[derive(Debug, Row_Ext)]
+struct Sample {
+    id: i32,
+    int_col: Option<i32>,
+    nvarchar_col: Option<String>,
+}
+
+#[tokio::test]
+#[ignore = "needs the test database"]
+async fn test_query_helpers() -> anyhow::Result<()> {
+    let test_app = DBTester::new("101.95.95.58", 1433, "sa", "Ibm123", "migrates/test.sql");
+    let mut client = test_app.get_client().await;
+
+    let all: Vec<Sample> = query_all(&mut client, "SELECT * FROM test", &[]).await?;
+    assert_eq!(all.len(), 1);
+    let one: Sample = query_one(
+        &mut client,
+        "SELECT * FROM test WHERE id = @P1",
+        &[&all[0].id],
+    )
+    .await?;
+    assert_eq!(one.id, all[0].id);
+    let count: i32 = query_one(&mut client, "SELECT COUNT(*) FROM test", &[]).await?;
+    assert_eq!(count, 1);
+    let pair: (i32, Option<String>) =
+        query_one(&mut client, "SELECT id, nvarchar_col FROM test", &[]).await?;
+    assert_eq!(pair.0, one.id);
+    let ids: Vec<i32> = query_as(&mut client, "SELECT id FROM test", &[]).await?;
+    assert_eq!(ids, [one.id]);
+
+    let none: Option<Sample> =
+        query_optional(&mut client, "SELECT * FROM test WHERE id < 0", &[]).await?;
+    assert!(none.is_none());
+    let err = query_one::<Sample, _>(&mut client, "SELECT * FROM test WHERE id < 0", &[])
+        .await
+        .err()
+        .unwrap();
+    assert_eq!(err.to_string(), "query returned no rows");
+    let err = query_one::<i32, _>(&mut client, "SELECT 1 UNION ALL SELECT 2", &[])
+        .await
+        .err()
+        .unwrap();
+    assert_eq!(
+        err.to_string(),
+        "query returned more than one row, expected one"
+    );
+    let err = query_optional::<i32, _>(
+        &mut client,
+        "SELECT value FROM GENERATE_SERIES(1, 1000000)",
+        &[],
+    )
+    .await
+    .err()
+    .unwrap();
+    assert_eq!(
+        err.to_string(),
+        "query returned more than one row, expected at most one"
+    );
+    // The rest of that result is discarded before the next query.
+    let count: i32 = query_one(&mut client, "SELECT COUNT(*) FROM test", &[]).await?;
+    assert_eq!(count, 1);
+    Ok(())
+}
+
+#[tokio::test]
+#[ignore = "needs the test database"]
+async fn test_query_stream() -> anyhow::Result<()> {
+    let test_app = DBTester::new("101.95.95.58", 1433, "sa", "Ibm123", "migrates/test.sql");
+    let mut client = test_app.get_client().await;
+    let numbers = "SELECT value FROM GENERATE_SERIES(1, 2500)";
+
+    let mut rows = query_stream::<i32, _>(&mut client, numbers, &[]).await?;
+    let mut sum = 0;
+    while let Some(n) = rows.next().await {
+        sum += n?;
+    }
+    drop(rows);
+    assert_eq!(sum, 2500 * 2501 / 2);
+
+    let stream = client.query(numbers, &[]).await?;
+    let mut chunks = RowStream::<(i32,)>::new(stream).chunks(1000);
+    let mut sizes = Vec::new();
+    while let Some(chunk) = chunks.next().await {
+        sizes.push(chunk?.len());
+    }
+    drop(chunks);
+    assert_eq!(sizes, [1000, 1000, 500]);
+
+    // Dropping a stream half way leaves the connection usable.
+    let mut rows = query_stream::<i32, _>(&mut client, numbers, &[]).await?;
+    assert_eq!(rows.next().await.transpose()?, Some(1));
+    drop(rows);
+    let count: i32 = query_one(&mut client, "SELECT COUNT(*) FROM test", &[]).await?;
+    assert_eq!(count, 1);
+    Ok(())
+}
@@ -0,0 +1,47 @@
+use tiberius_db_tester::DBTester;
+use tiberius_row::{query_results, FromResults};
+
+type Summary = (Vec<(i32, Option<String>)>, Vec<i32>, Option<i32>);
+
+#[test]
+fn test_from_results_empty_sets() -> anyhow::Result<()> {
+    let (ids, name, totals) =
+        <(Vec<i32>, Option<String>, Vec<(i32, f64)>)>::from_results(vec![vec![], vec![], vec![]])?;
+    assert!(ids.is_empty());
+    assert_eq!(name, None);
+    assert!(totals.is_empty());
+    Ok(())
+}
+
+#[test]
+fn test_from_results_arity() {
+    let err = <(Vec<i32>, Vec<i32>)>::from_results(vec![vec![], vec![], vec![]])
+        .err()
+        .unwrap();
+    assert_eq!(err.to_string(), "query returned 3 result sets, expected 2");
+    let err = <(Vec<i32>, Vec<i32>, Option<i32>)>::from_results(Vec::new())
+        .err()
+        .unwrap();
+    assert_eq!(err.to_string(), "query returned 0 result sets, expected 3");
+}
+
+#[tokio::test]
+#[ignore = "needs the test database"]
+async fn test_query_results() -> anyhow::Result<()> {
+    let test_app = DBTester::new("101.95.95.58", 1433, "sa", "Ibm123", "migrates/test.sql");
+    let mut client = test_app.get_client().await;
+    let sql = "SELECT id, nvarchar_col FROM test; \
+               SELECT id FROM test WHERE id < 0; \
+               SELECT COUNT(*) FROM test";
+    let (rows, empty, count): Summary = query_results(&mut client, sql, &[]).await?;
+    assert_eq!(rows.len(), 1);
+    assert!(empty.is_empty());
+    assert_eq!(count, Some(1));
+
+    let err = query_results::<(Vec<i32>, Vec<i32>), _>(&mut client, sql, &[])
+        .await
+        .err()
+        .unwrap();
+    assert_eq!(err.to_string(), "query returned 3 result sets, expected 2");
+    Ok(())
+}
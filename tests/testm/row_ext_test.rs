@@ -277,3 +277,84 @@ async fn test_row_ext() -> anyhow::Result<()> {
     println!("{:?}", test_row);
     Ok(())
 }
+
+#[derive(Debug, Deserialize, Row_Ext)]
+#[serde(rename_all = "PascalCase")]
+struct Customer {
+    customer_id: i32,
+    #[serde(rename = "full_name", alias = "Name")]
+    name: String,
+    #[serde(default)]
+    tags: Vec<u8>,
+    #[serde(default = "default_region")]
+    region: String,
+    #[row_ext(rename = "e_mail")]
+    email: Option<String>,
+}
+
+fn default_region() -> String {
+    "EU".to_owned()
+}
+
+#[test]
+fn test_serde_attrs() -> anyhow::Result<()> {
+    use tiberius::ColumnData;
+    use tiberius_row::RowMapper;
+
+    let cells = crate::helpers::cells(vec![
+        ("CustomerId", ColumnData::I32(Some(7))),
+        ("Name", ColumnData::String(Some("Alice".into()))),
+        ("e_mail", ColumnData::String(None)),
+    ]);
+    let customer = Customer::from_cells(cells.iter().map(|(c, v)| (c, v)))?;
+    assert_eq!(customer.customer_id, 7);
+    assert_eq!(customer.name, "Alice");
+    assert!(customer.tags.is_empty());
+    assert_eq!(customer.region, "EU");
+    assert_eq!(customer.email, None);
+
+    let columns = cells.iter().map(|(c, _)| c.clone()).collect::<Vec<_>>();
+    let mapper = RowMapper::<Customer>::new(&columns)?;
+    let customer = mapper.decode_cells(cells.iter().map(|(c, v)| (c, v)))?;
+    assert_eq!(customer.name, "Alice");
+    assert_eq!(customer.region, "EU");
+
+    let cells = crate::helpers::cells(vec![("CustomerId", ColumnData::I32(Some(7)))]);
+    let err = Customer::from_cells(cells.iter().map(|(c, v)| (c, v)))
+        .err()
+        .unwrap();
+    assert_eq!(err.to_string(), "column `full_name` not found");
+    Ok(())
+}
+
+#[derive(Debug, Deserialize, Row_Ext)]
+struct Session {
+    id: i32,
+    #[serde(skip)]
+    cache: Vec<String>,
+    #[serde(skip_deserializing, default = "default_region")]
+    region: String,
+}
+
+#[test]
+fn test_serde_skip() -> anyhow::Result<()> {
+    use tiberius::ColumnData;
+    use tiberius_row::RowMapper;
+
+    // Skipped fields take their default even when the row has their column.
+    let cells = crate::helpers::cells(vec![
+        ("id", ColumnData::I32(Some(3))),
+        ("cache", ColumnData::I32(Some(1))),
+    ]);
+    let session = Session::from_cells(cells.iter().map(|(c, v)| (c, v)))?;
+    assert_eq!(session.id, 3);
+    assert!(session.cache.is_empty());
+    assert_eq!(session.region, "EU");
+
+    let columns = cells.iter().map(|(c, _)| c.clone()).collect::<Vec<_>>();
+    let mapper = RowMapper::<Session>::new(&columns)?;
+    let session = mapper.decode_cells(cells.iter().map(|(c, v)| (c, v)))?;
+    assert!(session.cache.is_empty());
+    assert_eq!(session.region, "EU");
+    Ok(())
+}
@@ -0,0 +1,119 @@
+use std::collections::{BTreeSet, HashSet};
+
+use chrono::NaiveDate;
+use tiberius::{ColumnData, Uuid};
+use tiberius_row::Row_Ext;
+
+use crate::helpers::cells;
+
+#[allow(unused)]
+#[derive(Debug, Row_Ext)]
+struct Product {
+    #[row_ext(split = ",")]
+    tags: Vec<String>,
+    #[row_ext(split = ",", trim, skip_empty)]
+    sizes: BTreeSet<i32>,
+    #[row_ext(split = "|")]
+    colors: HashSet<String>,
+    #[row_ext(split = ",")]
+    related: Option<Vec<i64>>,
+    #[row_ext(split = ",")]
+    aliases: Vec<String>,
+}
+
+fn text(s: &str) -> ColumnData<'static> {
+    ColumnData::String(Some(s.to_string().into()))
+}
+
+#[test]
+fn test_split() -> anyhow::Result<()> {
+    let cells = cells(vec![
+        ("tags", text("new,sale, gift")),
+        ("sizes", text(" 42, 38,,40 ,38")),
+        ("colors", text("red|blue|red")),
+        ("related", ColumnData::String(None)),
+        ("aliases", ColumnData::String(None)),
+    ]);
+    let product = Product::from_cells(cells.iter().map(|(c, v)| (c, v)))?;
+    assert_eq!(product.tags, ["new", "sale", " gift"]);
+    assert_eq!(product.sizes, BTreeSet::from([38, 40, 42]));
+    assert_eq!(
+        product.colors,
+        HashSet::from(["red".to_string(), "blue".to_string()])
+    );
+    assert_eq!(product.related, None);
+    assert!(product.aliases.is_empty());
+    Ok(())
+}
+
+#[test]
+fn test_split_empty() -> anyhow::Result<()> {
+    let cells = cells(vec![
+        ("tags", text("")),
+        ("sizes", text("  ")),
+        ("colors", text("")),
+        ("related", text("")),
+        ("aliases", text("")),
+    ]);
+    let product = Product::from_cells(cells.iter().map(|(c, v)| (c, v)))?;
+    assert!(product.tags.is_empty());
+    assert!(product.sizes.is_empty());
+    assert!(product.colors.is_empty());
+    assert_eq!(product.related, Some(Vec::new()));
+    assert!(product.aliases.is_empty());
+    Ok(())
+}
+
+#[test]
+fn test_split_element_error() {
+    let cells = cells(vec![
+        ("tags", text("")),
+        ("sizes", text("42,,x38,40")),
+        ("colors", text("red")),
+        ("aliases", text("")),
+    ]);
+    let err = Product::from_cells(cells.iter().map(|(c, v)| (c, v))).unwrap_err();
+    let chain = err.chain().map(|e| e.to_string()).collect::<Vec<_>>();
+    assert_eq!(
+        chain,
+        [
+            "failed to decode column `sizes`",
+            "element 2 (`x38`)",
+            "invalid digit found in string",
+        ]
+    );
+}
+
+#[allow(unused)]
+#[derive(Debug, Row_Ext)]
+struct Batch {
+    #[row_ext(split = ",", trim)]
+    ids: Vec<Uuid>,
+    #[row_ext(split = ";")]
+    dates: BTreeSet<NaiveDate>,
+    #[row_ext(split = ",")]
+    flags: Vec<bool>,
+}
+
+#[test]
+fn test_split_typed() -> anyhow::Result<()> {
+    let cells = cells(vec![
+        (
+            "ids",
+            text("00000000-0000-0000-0000-000000000001, 00000000-0000-0000-0000-000000000002"),
+        ),
+        ("dates", text("2024-05-02;2024-05-01")),
+        ("flags", text("true,false")),
+    ]);
+    let batch = Batch::from_cells(cells.iter().map(|(c, v)| (c, v)))?;
+    assert_eq!(batch.ids, [Uuid::from_u128(1), Uuid::from_u128(2)]);
+    assert_eq!(
+        batch.dates.into_iter().collect::<Vec<_>>(),
+        [
+            NaiveDate::from_ymd_opt(2024, 5, 1).unwrap(),
+            NaiveDate::from_ymd_opt(2024, 5, 2).unwrap()
+        ]
+    );
+    assert_eq!(batch.flags, [true, false]);
+    Ok(())
+}
@@ -0,0 +1,105 @@
+use chrono::{DateTime, FixedOffset, NaiveDate, Utc};
+use chrono_tz::Tz;
+use tiberius::{
+    time::{Date, DateTime2, DateTimeOffset, Time},
+    ColumnData,
+};
+use tiberius_row::Row_Ext;
+
+use crate::helpers::cells;
+
+/// A `datetime2` column holding `days` since 0001-01-01 at `h:m`.
+fn datetime2(days: u32, h: u64, m: u64) -> ColumnData<'static> {
+    let secs = h * 3600 + m * 60;
+    ColumnData::DateTime2(Some(DateTime2::new(
+        Date::new(days),
+        Time::new(secs * 10_000_000, 7),
+    )))
+}
+
+/// 2023-01-01 12:30 as stored in a zone-less column.
+fn new_year() -> ColumnData<'static> {
+    datetime2(738_520, 12, 30)
+}
+
+fn utc(y: i32, mo: u32, d: u32, h: u32, mi: u32) -> DateTime<Utc> {
+    NaiveDate::from_ymd_opt(y, mo, d)
+        .unwrap()
+        .and_hms_opt(h, mi, 0)
+        .unwrap()
+        .and_utc()
+}
+
+#[allow(unused)]
+#[derive(Debug, Row_Ext)]
+struct Order {
+    #[row_ext(tz = "Asia/Shanghai")]
+    created_at: DateTime<Utc>,
+    #[row_ext(tz = "Asia/Shanghai")]
+    local_at: DateTime<FixedOffset>,
+    utc_at: DateTime<Utc>,
+}
+
+#[test]
+fn test_field_tz() -> anyhow::Result<()> {
+    let cells = cells(vec![
+        ("created_at", new_year()),
+        ("local_at", new_year()),
+        ("utc_at", new_year()),
+    ]);
+    let order = Order::from_cells(cells.iter().map(|(c, v)| (c, v)))?;
+    assert_eq!(order.created_at, utc(2023, 1, 1, 4, 30));
+    assert_eq!(order.local_at, utc(2023, 1, 1, 4, 30));
+    assert_eq!(order.local_at.offset().local_minus_utc(), 8 * 3600);
+    assert_eq!(order.utc_at, utc(2023, 1, 1, 12, 30));
+    Ok(())
+}
+
+#[allow(unused)]
+#[derive(Debug, Row_Ext)]
+#[row_ext(tz = "America/New_York")]
+struct Shift {
+    starts_at: DateTime<Tz>,
+    ends_at: Option<DateTime<Utc>>,
+}
+
+#[test]
+fn test_container_tz() -> anyhow::Result<()> {
+    let dto = DateTimeOffset::new(DateTime2::new(Date::new(738_520), Time::new(0, 7)), 60);
+    let cells = cells(vec![
+        ("starts_at", ColumnData::DateTimeOffset(Some(dto))),
+        ("ends_at", new_year()),
+    ]);
+    let shift = Shift::from_cells(cells.iter().map(|(c, v)| (c, v)))?;
+    assert_eq!(shift.starts_at, utc(2023, 1, 1, 0, 0));
+    assert_eq!(shift.starts_at.timezone(), Tz::America__New_York);
+    assert_eq!(shift.ends_at, Some(utc(2023, 1, 1, 17, 30)));
+    Ok(())
+}
+
+#[test]
+fn test_tz_dst_gap() {
+    // 2023-03-12 02:30 was skipped when New York moved to daylight time.
+    let cells = cells(vec![("starts_at", datetime2(738_590, 2, 30))]);
+    let err = Shift::from_cells(cells.iter().map(|(c, v)| (c, v))).unwrap_err();
+    assert_eq!(
+        err.root_cause().to_string(),
+        "2023-03-12 02:30:00 does not exist in America/New_York due to a DST gap"
+    );
+}
+
+#[allow(unused)]
+#[derive(Debug, Row_Ext)]
+struct Unzoned {
+    at: DateTime<Tz>,
+}
+
+#[test]
+fn test_tz_required() {
+    let cells = cells(vec![("at", new_year())]);
+    let err = Unzoned::from_cells(cells.iter().map(|(c, v)| (c, v))).unwrap_err();
+    assert!(err
+        .root_cause()
+        .to_string()
+        .starts_with("no time zone set for `chrono::datetime::DateTime<"));
+}
@@ -0,0 +1,56 @@
+use tiberius::ColumnData;
+use tiberius_row::Row_Ext;
+
+use crate::helpers::cells;
+
+#[derive(Debug, Row_Ext)]
+#[row_ext(update)]
+struct Account {
+    id: i32,
+    #[row_ext(rename = "AccountName")]
+    name: String,
+    balance: f64,
+    note: Option<String>,
+}
+
+fn account() -> Account {
+    Account {
+        id: 1,
+        name: "Alice".to_string(),
+        balance: 10.0,
+        note: Some("vip".to_string()),
+    }
+}
+
+#[test]
+fn test_update_from_cells() -> anyhow::Result<()> {
+    let mut account = account();
+    let cells = cells(vec![
+        ("id", ColumnData::I32(Some(1))),
+        ("balance", ColumnData::F64(Some(25.5))),
+        ("note", ColumnData::String(None)),
+    ]);
+    let changed = account.update_from_cells(cells.iter().map(|(c, v)| (c, v)))?;
+    assert_eq!(changed.iter().collect::<Vec<_>>(), ["balance", "note"]);
+    assert!(!changed.contains("id"));
+    assert_eq!(account.name, "Alice");
+    assert_eq!(account.balance, 25.5);
+    assert_eq!(account.note, None);
+    Ok(())
+}
+
+#[test]
+fn test_update_error_keeps_struct() {
+    let mut account = account();
+    let cells = cells(vec![
+        ("balance", ColumnData::F64(Some(25.5))),
+        ("AccountName", ColumnData::I32(Some(3))),
+        ("id", ColumnData::String(Some("two".into()))),
+    ]);
+    let err = account
+        .update_from_cells(cells.iter().map(|(c, v)| (c, v)))
+        .unwrap_err();
+    assert_eq!(err.to_string(), "failed to decode column `id`");
+    assert_eq!(account.balance, 10.0);
+    assert_eq!(account.name, "Alice");
+}
@@ -0,0 +1,91 @@
+use std::borrow::Cow;
+
+use serde::Deserialize;
+use tiberius::{xml::XmlData, ColumnData};
+use tiberius_row::{Row_Ext, XmlDocument};
+
+use crate::helpers::cells;
+
+fn xml(text: &str) -> ColumnData<'static> {
+    ColumnData::Xml(Some(Cow::Owned(XmlData::new(text))))
+}
+
+#[derive(Debug, PartialEq, Deserialize)]
+struct Root {
+    child: String,
+}
+
+#[derive(Debug, PartialEq, Deserialize)]
+struct Audit {
+    #[serde(rename = "@user")]
+    user: String,
+    action: Vec<String>,
+}
+
+#[allow(unused)]
+#[derive(Debug, Row_Ext)]
+struct Config {
+    #[row_ext(xml)]
+    xml_col: Root,
+    #[row_ext(xml)]
+    audit: Option<Audit>,
+    #[row_ext(rename = "xml_col")]
+    document: XmlDocument,
+}
+
+#[test]
+fn test_xml() -> anyhow::Result<()> {
+    let cells = cells(vec![
+        ("xml_col", xml("<root><child>Hello, World!</child></root>")),
+        (
+            "audit",
+            ColumnData::String(Some(
+                r#"<audit user="alice"><action>create</action><action>approve</action></audit>"#
+                    .into(),
+            )),
+        ),
+    ]);
+    let config = Config::from_cells(cells.iter().map(|(c, v)| (c, v)))?;
+    assert_eq!(config.xml_col.child, "Hello, World!");
+    assert_eq!(
+        config.audit,
+        Some(Audit {
+            user: "alice".to_string(),
+            action: vec!["create".to_string(), "approve".to_string()],
+        })
+    );
+    let document = config.document.document();
+    let child = document.descendants().find(|n| n.has_tag_name("child"));
+    assert_eq!(child.and_then(|n| n.text()), Some("Hello, World!"));
+    Ok(())
+}
+
+#[test]
+fn test_xml_null() -> anyhow::Result<()> {
+    let cells = cells(vec![
+        ("xml_col", xml("<root><child/></root>")),
+        ("audit", ColumnData::Xml(None)),
+    ]);
+    let config = Config::from_cells(cells.iter().map(|(c, v)| (c, v)))?;
+    assert_eq!(config.audit, None);
+    Ok(())
+}
+
+#[test]
+fn test_xml_errors() {
+    let mismatched = cells(vec![("xml_col", xml("<root><other/></root>"))]);
+    let err = Config::from_cells(mismatched.iter().map(|(c, v)| (c, v))).unwrap_err();
+    assert_eq!(err.to_string(), "failed to decode column `xml_col`");
+    assert!(err
+        .chain()
+        .any(|e| e.to_string().starts_with("XML does not match `")));
+
+    let not_xml = cells(vec![
+        ("xml_col", xml("<root><child/></root>")),
+        ("audit", ColumnData::I32(Some(1))),
+    ]);
+    let err = Config::from_cells(not_xml.iter().map(|(c, v)| (c, v))).unwrap_err();
+    assert_eq!(err.root_cause().to_string(), "cannot decode int as XML");
+
+    assert!(XmlDocument::parse("<root>".to_string()).is_err());
+}
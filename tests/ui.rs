@@ -0,0 +1,15 @@
+//! Errors the derives report at compile time, checked with trybuild. Cases
+//! that need a feature of tiberius_row are in a directory named after it and
+//! only run with that feature.
+
+#[test]
+fn ui() {
+    let t = trybuild::TestCases::new();
+    t.compile_fail("tests/ui/*.rs");
+    #[cfg(feature = "chrono-tz")]
+    t.compile_fail("tests/ui/chrono-tz/*.rs");
+    #[cfg(feature = "encoding")]
+    t.compile_fail("tests/ui/encoding/*.rs");
+    #[cfg(feature = "compress")]
+    t.compile_fail("tests/ui/compress/*.rs");
+}
@@ -0,0 +1,16 @@
+use chrono::{DateTime, NaiveDateTime, Utc};
+use tiberius_row::Row_Ext;
+
+#[derive(Row_Ext)]
+struct Event {
+    #[row_ext(tz = "Mars/Olympus_Mons")]
+    at: DateTime<Utc>,
+}
+
+#[derive(Row_Ext)]
+#[row_ext(tz = "Asia/Atlantis")]
+struct Log {
+    created: NaiveDateTime,
+}
+
+fn main() {}
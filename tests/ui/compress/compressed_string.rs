@@ -0,0 +1,9 @@
+use tiberius_row::Row_Ext;
+
+#[derive(Row_Ext)]
+struct Document {
+    #[row_ext(compressed)]
+    body: String,
+}
+
+fn main() {}
@@ -0,0 +1,12 @@
+use tiberius::numeric::Decimal;
+use tiberius_row::Procedure;
+
+#[derive(Procedure)]
+#[proc(name = "dbo.place_order")]
+struct PlaceOrder {
+    customer_id: i32,
+    #[proc(output)]
+    total: Option<Decimal>,
+}
+
+fn main() {}
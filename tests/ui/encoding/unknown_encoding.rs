@@ -0,0 +1,15 @@
+use tiberius_row::{Row_Ext, ToParams};
+
+#[derive(Row_Ext)]
+struct Customer {
+    #[row_ext(encoding = "klingon")]
+    name: String,
+}
+
+#[derive(ToParams)]
+struct CustomerParams {
+    #[row_ext(encoding = "klingon")]
+    name: String,
+}
+
+fn main() {}
@@ -0,0 +1,16 @@
+use serde::Deserialize;
+use tiberius_row::Row_Ext;
+
+#[derive(Deserialize)]
+struct Address {
+    city: String,
+}
+
+#[derive(Row_Ext, Deserialize)]
+struct Customer {
+    id: i32,
+    #[serde(flatten)]
+    address: Address,
+}
+
+fn main() {}
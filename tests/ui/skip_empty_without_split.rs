@@ -0,0 +1,9 @@
+use tiberius_row::Row_Ext;
+
+#[derive(Row_Ext)]
+struct Article {
+    #[row_ext(skip_empty)]
+    tags: Vec<String>,
+}
+
+fn main() {}
@@ -0,0 +1,12 @@
+use tiberius_row::ToParams;
+
+#[derive(ToParams)]
+struct Order {
+    id: i32,
+    #[row_ext(json_path = "$.city")]
+    city: String,
+    #[row_ext(try_from = "i64")]
+    quantity: u32,
+}
+
+fn main() {}
@@ -0,0 +1,27 @@
+use params::process_to_params;
+use proc_macro::TokenStream;
+use procedure::process_procedure;
+use rowext::process_row_ext;
+
+mod params;
+mod procedure;
+mod rowext;
+mod serde_attrs;
+
+#[proc_macro_derive(Row_Ext, attributes(row_ext))]
+pub fn derive_row_ext(input: TokenStream) -> TokenStream {
+    let input = syn::parse_macro_input!(input as syn::DeriveInput);
+    process_row_ext(input).into()
+}
+
+#[proc_macro_derive(ToParams, attributes(row_ext))]
+pub fn derive_to_params(input: TokenStream) -> TokenStream {
+    let input = syn::parse_macro_input!(input as syn::DeriveInput);
+    process_to_params(input).into()
+}
+
+#[proc_macro_derive(Procedure, attributes(proc))]
+pub fn derive_procedure(input: TokenStream) -> TokenStream {
+    let input = syn::parse_macro_input!(input as syn::DeriveInput);
+    process_procedure(input).into()
+}
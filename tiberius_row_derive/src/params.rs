@@ -0,0 +1,269 @@
+use darling::FromDeriveInput;
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::ext::IdentExt as _;
+use syn::DeriveInput;
+
+use crate::rowext::{check_encoding, BoolFrom, Compressed, FieldData, StructData};
+use crate::serde_attrs::{Direction, SerdeAttrs};
+
+/// Serde options that change how a field is serialized in a way `ToParams`
+/// can't follow.
+const UNSUPPORTED_SERDE: &[&str] = &["serialize_with", "with", "flatten", "getter"];
+
+/// The same for the struct.
+const UNSUPPORTED_SERDE_CONTAINER: &[&str] = &["into", "transparent", "remote"];
+
+/// For a `#[row_ext]` option that changes how a column is read in a way
+/// `ToParams` can't reverse.
+fn irreversible(option: &str) -> darling::Error {
+    darling::Error::custom(format!(
+        "`#[row_ext({})]` cannot be written back by `ToParams`",
+        option
+    ))
+}
+
+/// The statements binding `field` to `params`, or `None` for a field that
+/// isn't written.
+///
+/// The options are the ones `Row_Ext` parses, so each is written back the
+/// way it is read: first to text, through `json`, `bool_from`, `from_str` or
+/// `split`, then to bytes in a code page with `encoding`, then compressed.
+/// Options that only clean up the text before it is decoded, like `trim`,
+/// are ignored.
+fn push(field: &FieldData, container: &SerdeAttrs) -> darling::Result<Option<TokenStream>> {
+    // Destructured in full, so that an option added to `Row_Ext` has to be
+    // handled here too.
+    let FieldData {
+        ident,
+        ty,
+        attrs,
+        rename,
+        serde,
+        tz,
+        json,
+        json_path,
+        xml,
+        split,
+        trim: _,
+        empty_as_none: _,
+        bool_from,
+        compressed,
+        encoding,
+        lossy,
+        skip_empty: _,
+        borrow: _,
+        extra,
+        wkt,
+        wkb,
+        from_str,
+        try_from,
+        column: _,
+        aliases: _,
+        default: _,
+        skip: _,
+    } = field;
+    let ident = ident.as_ref().unwrap();
+    for (set, option) in [
+        (*serde, "serde"),
+        (tz.is_some(), "tz"),
+        (json_path.is_some(), "json_path"),
+        (*xml, "xml"),
+        (*wkt, "wkt"),
+        (*wkb, "wkb"),
+        (try_from.is_some(), "try_from"),
+    ] {
+        if set {
+            return Err(irreversible(option).with_span(ident));
+        }
+    }
+    let serde = SerdeAttrs::parse(attrs, Direction::Serialize, "ToParams", UNSUPPORTED_SERDE)?;
+    if *extra || serde.skip {
+        return Ok(None);
+    }
+    let column = match rename {
+        Some(rename) => rename.clone(),
+        None => {
+            let name = ident.unraw().to_string();
+            match (serde.rename, container.rename_all) {
+                (Some(rename), _) => rename,
+                (None, Some(rule)) => rule.apply(&name),
+                (None, None) => name,
+            }
+        }
+    };
+
+    #[derive(PartialEq)]
+    enum Value {
+        Field,
+        Text,
+        Bytes,
+    }
+
+    let p = quote! { ::tiberius_row::__private::params };
+    let mut steps = Vec::new();
+    let mut value = Value::Field;
+    if *json {
+        steps.push(quote! {
+            let value = ::tiberius_row::__private::json_text(&self.#ident)?;
+            let value = value.as_deref();
+        });
+        value = Value::Text;
+    } else if let Some(BoolFrom(yes, no)) = bool_from {
+        steps.push(quote! { let value = #p::bool_text(&self.#ident, [#yes, #no]); });
+        value = Value::Text;
+    } else if *from_str {
+        let inner = inner_type(ty);
+        steps.push(quote! {
+            let value = #p::display::<#inner>(&self.#ident);
+            let value = value.as_deref();
+        });
+        value = Value::Text;
+    } else if let Some(sep) = split {
+        let inner = inner_type(ty);
+        steps.push(quote! {
+            let value = #p::join::<#inner>(&self.#ident, #sep);
+            let value = value.as_deref();
+        });
+        value = Value::Text;
+    }
+
+    // As when reading, the code page only applies to bytes, which a
+    // `compressed` text encoding doesn't leave.
+    let text_compressed = matches!(compressed, Some(Compressed::Utf8 | Compressed::Utf16));
+    if let Some(label) = encoding {
+        check_encoding(label)?;
+        if !text_compressed {
+            if value == Value::Field {
+                steps.push(quote! { let value = #p::text(&self.#ident); });
+            }
+            steps.push(quote! {
+                let value = #p::encode(
+                    value,
+                    ::tiberius_row::__private::Encoding { label: #label, lossy: #lossy },
+                )?;
+                let value = value.as_deref();
+            });
+            value = Value::Bytes;
+        }
+    }
+
+    if let Some(compressed) = *compressed {
+        if !cfg!(feature = "compress") {
+            return Err(darling::Error::custom(
+                "`compressed` requires the `compress` feature of tiberius_row",
+            )
+            .with_span(ident));
+        }
+        let kind = compressed.tokens();
+        steps.push(match (&value, compressed) {
+            (Value::Bytes, _) => quote! { let value = #p::compress(value); },
+            (Value::Field, Compressed::Bytes) => {
+                quote! { let value = #p::compress(#p::bytes(&self.#ident)); }
+            }
+            (Value::Field, _) => {
+                quote! { let value = #p::compress_text(#p::text(&self.#ident), #kind); }
+            }
+            (Value::Text, _) => quote! { let value = #p::compress_text(value, #kind); },
+        });
+        value = Value::Bytes;
+    }
+
+    let push = if value == Value::Field {
+        quote! { params.push(#column, &self.#ident); }
+    } else {
+        quote! {
+            {
+                #(#steps)*
+                params.push(#column, &value);
+            }
+        }
+    };
+    // An absent `Field` has nothing to write, not even NULL.
+    Ok(Some(if is_field(ty) {
+        quote! {
+            if !self.#ident.is_absent() {
+                #push
+            }
+        }
+    } else {
+        push
+    }))
+}
+
+/// Whether the field is typed `Field<T>`.
+fn is_field(ty: &syn::Type) -> bool {
+    match ty {
+        syn::Type::Path(path) => path
+            .path
+            .segments
+            .last()
+            .is_some_and(|segment| segment.ident == "Field"),
+        _ => false,
+    }
+}
+
+/// The `T` of a field typed `T`, `Option<T>` or `Field<T>`.
+fn inner_type(ty: &syn::Type) -> &syn::Type {
+    if let syn::Type::Path(path) = ty {
+        if let Some(segment) = path.path.segments.last() {
+            if segment.ident == "Option" || segment.ident == "Field" {
+                if let syn::PathArguments::AngleBracketed(args) = &segment.arguments {
+                    if let Some(syn::GenericArgument::Type(inner)) = args.args.first() {
+                        return inner;
+                    }
+                }
+            }
+        }
+    }
+    ty
+}
+
+pub(crate) fn process_to_params(input: DeriveInput) -> TokenStream {
+    match to_params(&input) {
+        Ok(tokens) => tokens,
+        Err(e) => e.write_errors(),
+    }
+}
+
+fn to_params(input: &DeriveInput) -> darling::Result<TokenStream> {
+    // `update` only changes what `Row_Ext` generates.
+    let StructData {
+        ident,
+        generics,
+        attrs,
+        data,
+        tz,
+        update: _,
+    } = StructData::from_derive_input(input)?;
+    if let Some(tz) = tz {
+        return Err(irreversible("tz").with_span(&tz));
+    }
+    let container = SerdeAttrs::parse(
+        &attrs,
+        Direction::Serialize,
+        "ToParams",
+        UNSUPPORTED_SERDE_CONTAINER,
+    )?;
+    let mut errors = darling::Error::accumulator();
+    let pushes = data
+        .take_struct()
+        .unwrap()
+        .fields
+        .iter()
+        .filter_map(|f| errors.handle(push(f, &container)).flatten())
+        .collect::<Vec<_>>();
+    errors.finish()?;
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics ::tiberius_row::ToParams for #ident #ty_generics #where_clause {
+            fn to_params(
+                &self,
+            ) -> Result<::tiberius_row::Params, ::tiberius_row::__private::anyhow::Error> {
+                let mut params = ::tiberius_row::Params::default();
+                #(#pushes)*
+                Ok(params)
+            }
+        }
+    })
+}
@@ -0,0 +1,281 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{Data, DeriveInput, Fields};
+
+/// The struct's `#[proc(name = "...", results = "...")]`.
+struct ProcOptions {
+    name: String,
+    results: syn::Type,
+}
+
+impl ProcOptions {
+    fn parse(input: &DeriveInput) -> syn::Result<Self> {
+        let mut name = None;
+        let mut results = None;
+        for attr in &input.attrs {
+            if !attr.path().is_ident("proc") {
+                continue;
+            }
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("name") {
+                    let s: syn::LitStr = meta.value()?.parse()?;
+                    name = Some(s.value());
+                } else if meta.path.is_ident("results") {
+                    let s: syn::LitStr = meta.value()?.parse()?;
+                    results = Some(s.parse()?);
+                } else {
+                    return Err(meta.error("expected `name` or `results`"));
+                }
+                Ok(())
+            })?;
+        }
+        let name = name.ok_or_else(|| {
+            syn::Error::new_spanned(
+                &input.ident,
+                "`Procedure` needs the procedure's name: #[proc(name = \"dbo.MyProc\")]",
+            )
+        })?;
+        Ok(ProcOptions {
+            name,
+            results: results.unwrap_or_else(|| syn::parse_quote!(())),
+        })
+    }
+}
+
+/// A field's `#[proc(rename = "...", output, sql_type = "...")]`.
+#[derive(Default)]
+struct FieldOptions {
+    rename: Option<String>,
+    output: bool,
+    sql_type: Option<String>,
+}
+
+impl FieldOptions {
+    fn parse(attrs: &[syn::Attribute]) -> syn::Result<Self> {
+        let mut options = FieldOptions::default();
+        for attr in attrs {
+            if !attr.path().is_ident("proc") {
+                continue;
+            }
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("rename") {
+                    let s: syn::LitStr = meta.value()?.parse()?;
+                    options.rename = Some(s.value().trim_start_matches('@').to_owned());
+                } else if meta.path.is_ident("output") {
+                    options.output = true;
+                } else if meta.path.is_ident("sql_type") {
+                    let s: syn::LitStr = meta.value()?.parse()?;
+                    options.sql_type = Some(s.value());
+                } else {
+                    return Err(meta.error("expected `rename`, `output` or `sql_type`"));
+                }
+                Ok(())
+            })?;
+        }
+        Ok(options)
+    }
+}
+
+/// The type an OUTPUT variable is declared with, from the field's Rust type
+/// with any `Option` removed. `Decimal` has no type to infer: a precision and
+/// scale other than the parameter's would round its value.
+fn infer_sql_type(ty: &syn::Type) -> Option<&'static str> {
+    let syn::Type::Path(path) = ty else {
+        return None;
+    };
+    let segment = path.path.segments.last()?;
+    let inner = match &segment.arguments {
+        syn::PathArguments::AngleBracketed(args) => match args.args.first() {
+            Some(syn::GenericArgument::Type(inner)) => Some(inner),
+            _ => None,
+        },
+        _ => None,
+    };
+    let sql_type = match segment.ident.to_string().as_str() {
+        "Option" => return infer_sql_type(inner?),
+        "Vec" => match inner? {
+            syn::Type::Path(inner) if inner.path.is_ident("u8") => "varbinary(max)",
+            _ => return None,
+        },
+        "bool" => "bit",
+        "u8" => "tinyint",
+        "i16" => "smallint",
+        "i32" => "int",
+        "i64" => "bigint",
+        "f32" => "real",
+        "f64" => "float",
+        "String" => "nvarchar(max)",
+        "Uuid" => "uniqueidentifier",
+        "NaiveDate" => "date",
+        "NaiveTime" => "time",
+        "NaiveDateTime" => "datetime2",
+        "DateTime" => "datetimeoffset",
+        _ => return None,
+    };
+    Some(sql_type)
+}
+
+/// Whether the field type is `Decimal`, or an `Option` of it.
+fn is_decimal(ty: &syn::Type) -> bool {
+    let syn::Type::Path(path) = ty else {
+        return false;
+    };
+    let Some(segment) = path.path.segments.last() else {
+        return false;
+    };
+    match &segment.arguments {
+        syn::PathArguments::AngleBracketed(args) if segment.ident == "Option" => {
+            matches!(args.args.first(), Some(syn::GenericArgument::Type(inner)) if is_decimal(inner))
+        }
+        _ => segment.ident == "Decimal",
+    }
+}
+
+pub(crate) fn process_procedure(input: DeriveInput) -> TokenStream {
+    match procedure(input) {
+        Ok(tokens) => tokens,
+        Err(e) => e.to_compile_error(),
+    }
+}
+
+fn procedure(input: DeriveInput) -> syn::Result<TokenStream> {
+    let fields = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => &fields.named,
+            _ => return Err(unsupported(&input)),
+        },
+        _ => return Err(unsupported(&input)),
+    };
+    let options = ProcOptions::parse(&input)?;
+
+    // Every field is bound in order as @P1, @P2 and so on. An OUTPUT field's
+    // value initialises a variable that is passed in its place and selected
+    // after the call, next to the RETURN status. The variables are named
+    // after the parameter's number so no field name can clash with them.
+    let mut declare = vec!["@__return_status int".to_owned()];
+    let mut args = Vec::new();
+    let mut select = vec!["@__return_status AS [__return_status]".to_owned()];
+    let mut pushes = Vec::new();
+    let mut outputs = Vec::new();
+    for (i, field) in fields.iter().enumerate() {
+        let field_options = FieldOptions::parse(&field.attrs)?;
+        let ident = field.ident.as_ref().unwrap();
+        let name = field_options.rename.unwrap_or_else(|| ident.to_string());
+        let placeholder = format!("@P{}", i + 1);
+        pushes.push(quote! { params.push(#name, &self.#ident); });
+        if !field_options.output {
+            args.push(format!("@{} = {}", name, placeholder));
+            continue;
+        }
+        let sql_type = match field_options.sql_type {
+            Some(sql_type) => sql_type,
+            None => infer_sql_type(&field.ty)
+                .ok_or_else(|| {
+                    syn::Error::new_spanned(
+                        &field.ty,
+                        if is_decimal(&field.ty) {
+                            "a Decimal OUTPUT parameter needs its precision and scale, set them with #[proc(sql_type = \"decimal(p, s)\")]"
+                        } else {
+                            "cannot infer the SQL type of this OUTPUT parameter, set it with #[proc(sql_type = \"...\")]"
+                        },
+                    )
+                })?
+                .to_owned(),
+        };
+        let column = format!("__out_{}", i + 1);
+        let var = format!("@{}", column);
+        declare.push(format!("{} {} = {}", var, sql_type, placeholder));
+        args.push(format!("@{} = {} OUTPUT", name, var));
+        select.push(format!("{} AS [{}]", var, column));
+        let index = outputs.len() + 1;
+        let ident_str = ident.to_string();
+        outputs.push(quote! {
+            self.#ident = ::tiberius_row::__private::decode_field(
+                cells,
+                &::tiberius_row::__private::Field::new(#index, #ident_str, #column),
+            )?;
+        });
+    }
+    let proc_name = &options.name;
+    let mut exec = format!("EXEC @__return_status = {}", proc_name);
+    if !args.is_empty() {
+        exec.push(' ');
+        exec.push_str(&args.join(", "));
+    }
+    let sql = format!(
+        "DECLARE {};\n{};\nSELECT {};",
+        declare.join(", "),
+        exec,
+        select.join(", ")
+    );
+
+    // A single result set is decoded as a one-element tuple and unwrapped.
+    let results = &options.results;
+    let decode_results = match results {
+        syn::Type::Tuple(_) => quote! {
+            <#results as ::tiberius_row::FromResults>::from_results(results)
+        },
+        _ => quote! {
+            <(#results,) as ::tiberius_row::FromResults>::from_results(results)
+                .map(|(results,)| results)
+        },
+    };
+
+    let ident = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics ::tiberius_row::Procedure for #ident #ty_generics #where_clause {
+            type Results = #results;
+
+            const NAME: &'static str = #proc_name;
+            const SQL: &'static str = #sql;
+
+            fn params(&self) -> ::tiberius_row::Params {
+                let mut params = ::tiberius_row::Params::default();
+                #(#pushes)*
+                params
+            }
+
+            fn read_outputs(
+                &mut self,
+                cells: &::tiberius_row::__private::Cells<'_, '_>,
+            ) -> Result<(), ::tiberius_row::__private::anyhow::Error> {
+                #(#outputs)*
+                Ok(())
+            }
+
+            fn decode_results(
+                results: ::std::vec::Vec<::std::vec::Vec<::tiberius_row::__private::tiberius::Row>>,
+            ) -> Result<#results, ::tiberius_row::__private::anyhow::Error> {
+                #decode_results
+            }
+        }
+
+        impl #impl_generics #ident #ty_generics #where_clause {
+            /// Runs the procedure, returning its result sets, OUTPUT values and
+            /// RETURN status.
+            pub async fn exec<S>(
+                self,
+                client: &mut ::tiberius_row::__private::tiberius::Client<S>,
+            ) -> Result<
+                ::tiberius_row::ProcResponse<Self, #results>,
+                ::tiberius_row::__private::anyhow::Error,
+            >
+            where
+                S: ::tiberius_row::__private::AsyncRead
+                    + ::tiberius_row::__private::AsyncWrite
+                    + Unpin
+                    + Send,
+            {
+                ::tiberius_row::exec_procedure(client, self).await
+            }
+        }
+    })
+}
+
+fn unsupported(input: &DeriveInput) -> syn::Error {
+    syn::Error::new_spanned(
+        &input.ident,
+        "`Procedure` only supports structs with named fields",
+    )
+}
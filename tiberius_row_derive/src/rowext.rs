@@ -0,0 +1,791 @@
+use darling::{ast, FromDeriveInput, FromField, FromMeta};
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::ext::IdentExt as _;
+use syn::DeriveInput;
+
+use crate::serde_attrs::{Direction, SerdeAttrs};
+
+/// Serde options that change how a field is deserialized in a way `Row_Ext`
+/// can't follow.
+const UNSUPPORTED_SERDE: &[&str] = &["deserialize_with", "with", "flatten"];
+
+/// The same for the struct.
+const UNSUPPORTED_SERDE_CONTAINER: &[&str] =
+    &["default", "from", "try_from", "transparent", "remote"];
+
+#[derive(Debug, FromDeriveInput)]
+#[darling(attributes(row_ext), forward_attrs(serde), supports(struct_named))]
+pub(crate) struct StructData {
+    pub(crate) ident: syn::Ident,
+    pub(crate) generics: syn::Generics,
+    pub(crate) attrs: Vec<syn::Attribute>,
+    pub(crate) data: ast::Data<(), FieldData>,
+    /// Time zone for zone-less datetime columns of every field.
+    #[darling(default)]
+    pub(crate) tz: Option<syn::LitStr>,
+    /// Also generate `update_from_row`, which needs `PartialEq` fields.
+    #[darling(default)]
+    pub(crate) update: bool,
+}
+
+#[derive(Debug, FromField)]
+#[darling(attributes(row_ext), forward_attrs(serde))]
+pub(crate) struct FieldData {
+    pub(crate) ident: Option<syn::Ident>,
+    pub(crate) ty: syn::Type,
+    pub(crate) attrs: Vec<syn::Attribute>,
+    /// Column name, when it differs from the field name.
+    #[darling(default)]
+    pub(crate) rename: Option<String>,
+    /// Decode through serde from the column's JSON form instead of `SqlDecode`.
+    #[darling(default)]
+    pub(crate) serde: bool,
+    /// Time zone for a zone-less datetime column.
+    #[darling(default)]
+    pub(crate) tz: Option<syn::LitStr>,
+    /// Deserialize the JSON text stored in the column.
+    #[darling(default)]
+    pub(crate) json: bool,
+    /// Take only the part of the JSON document at this path. Implies `json`.
+    #[darling(default)]
+    pub(crate) json_path: Option<String>,
+    /// Deserialize the column's XML with quick-xml.
+    #[darling(default)]
+    pub(crate) xml: bool,
+    /// Split the text on this separator into a collection.
+    #[darling(default)]
+    pub(crate) split: Option<String>,
+    /// Trim whitespace from the text, or from each `split` element.
+    #[darling(default)]
+    pub(crate) trim: Option<Trim>,
+    /// Read an empty string as NULL.
+    #[darling(default)]
+    pub(crate) empty_as_none: bool,
+    /// Read a `bool` from the two strings for true and false.
+    #[darling(default)]
+    pub(crate) bool_from: Option<BoolFrom>,
+    /// Decompress a binary column written with `COMPRESS()`.
+    #[darling(default)]
+    pub(crate) compressed: Option<Compressed>,
+    /// Decode a binary column as text in this code page.
+    #[darling(default)]
+    pub(crate) encoding: Option<syn::LitStr>,
+    /// Replace invalid `encoding` sequences instead of failing.
+    #[darling(default)]
+    pub(crate) lossy: bool,
+    /// Drop empty `split` elements.
+    #[darling(default)]
+    pub(crate) skip_empty: bool,
+    /// Borrow from the row with `SqlDecodeRef`. Implied for `&str` and
+    /// `&[u8]`.
+    #[darling(default)]
+    pub(crate) borrow: bool,
+    /// Collect the columns no other field reads.
+    #[darling(default)]
+    pub(crate) extra: bool,
+    /// Read a geometry from WKT text.
+    #[darling(default)]
+    pub(crate) wkt: bool,
+    /// Read a geometry from WKB bytes.
+    #[darling(default)]
+    pub(crate) wkb: bool,
+    /// Parse the column text with `FromStr`.
+    #[darling(default)]
+    pub(crate) from_str: bool,
+    /// Decode the column as this type, then convert it with `TryFrom`.
+    #[darling(default)]
+    pub(crate) try_from: Option<syn::Type>,
+    /// The column name, other names it may have and the value for a missing
+    /// column, from the options above and `#[serde]`. Set by
+    /// [`FieldData::resolve_serde`].
+    #[darling(skip)]
+    pub(crate) column: String,
+    #[darling(skip)]
+    pub(crate) aliases: Vec<String>,
+    #[darling(skip)]
+    pub(crate) default: Option<TokenStream>,
+    /// `#[serde(skip)]` or `skip_deserializing`: no column is read and the
+    /// field takes its default.
+    #[darling(skip)]
+    pub(crate) skip: bool,
+}
+
+impl FieldData {
+    /// Reads the field's `#[serde]` options, so structs written for the
+    /// serde based decoding keep working. The column is `#[row_ext(rename)]`,
+    /// then `#[serde(rename)]`, then the field name under the struct's
+    /// `rename_all`. `alias` adds other column names and `default` provides
+    /// the value when the row has none of them, or always for a skipped field.
+    fn resolve_serde(&mut self, container: &SerdeAttrs) -> syn::Result<()> {
+        let serde = SerdeAttrs::parse(
+            &self.attrs,
+            Direction::Deserialize,
+            "Row_Ext",
+            UNSUPPORTED_SERDE,
+        )?;
+        let name = self.ident.as_ref().unwrap().unraw().to_string();
+        self.column = match (&self.rename, serde.rename, container.rename_all) {
+            (Some(rename), _, _) => rename.clone(),
+            (None, Some(rename), _) => rename,
+            (None, None, Some(rule)) => rule.apply(&name),
+            (None, None, None) => name,
+        };
+        self.aliases = serde.aliases;
+        self.default = serde.default;
+        self.skip = serde.skip;
+        Ok(())
+    }
+
+    /// Whether the field is decoded from a column of its own, with an entry
+    /// in the struct's table.
+    fn reads_column(&self) -> bool {
+        !self.extra && !self.skip
+    }
+
+    /// Whether the field borrows from the row.
+    fn borrows(&self) -> bool {
+        self.borrow || is_reference(&self.ty)
+    }
+
+    /// Whether the field is decoded some other way than with `SqlDecode`.
+    fn custom_decode(&self) -> bool {
+        self.json
+            || self.json_path.is_some()
+            || self.xml
+            || self.serde
+            || self.split.is_some()
+            || self.from_str
+            || self.try_from.is_some()
+            || self.wkt
+            || self.wkb
+    }
+
+    /// Whether the column's value is changed before it is decoded.
+    fn normalizes(&self) -> bool {
+        self.trim.is_some()
+            || self.empty_as_none
+            || self.bool_from.is_some()
+            || self.compressed.is_some()
+            || self.encoding.is_some()
+    }
+
+    /// The check of the column's type done once by `RowMapper`, which only
+    /// plain `SqlDecode` fields have.
+    fn accepts(&self) -> Option<TokenStream> {
+        if self.custom_decode() || self.normalizes() || self.borrows() {
+            return None;
+        }
+        let ty = &self.ty;
+        Some(quote! { <#ty as ::tiberius_row::SqlDecode>::accepts })
+    }
+
+    /// The field's entry in the struct's table of `__private::Field`s, at
+    /// `index`.
+    fn field(&self, index: usize) -> TokenStream {
+        let name = self.ident.as_ref().unwrap().to_string();
+        let column = &self.column;
+        let mut options = Vec::new();
+        if !self.aliases.is_empty() {
+            let aliases = &self.aliases;
+            options.push(quote! { aliases: &[#(#aliases),*] });
+        }
+        if let Some(accepts) = self.accepts() {
+            options.push(quote! { accepts: #accepts });
+        }
+        if self.split.is_none() {
+            if let Some(trim) = self.trim.map(Trim::tokens) {
+                options.push(quote! { trim: Some(#trim) });
+            }
+        }
+        if self.empty_as_none {
+            options.push(quote! { empty_as_none: true });
+        }
+        if let Some(BoolFrom(yes, no)) = &self.bool_from {
+            options.push(quote! { bool_from: Some([#yes, #no]) });
+        }
+        if let Some(compressed) = self.compressed {
+            let compressed = compressed.tokens();
+            options.push(quote! { compressed: Some(#compressed) });
+        }
+        if let Some(label) = &self.encoding {
+            let lossy = self.lossy;
+            options.push(quote! {
+                encoding: Some(::tiberius_row::__private::Encoding { label: #label, lossy: #lossy })
+            });
+        }
+        let field = quote! { ::tiberius_row::__private::Field::new(#index, #name, #column) };
+        if options.is_empty() {
+            field
+        } else {
+            quote! { ::tiberius_row::__private::Field { #(#options,)* ..#field } }
+        }
+    }
+
+    /// A bare `compressed` leaves bytes, which only a `Vec<u8>` field or
+    /// `encoding` can take: any other field would read them as base64 text.
+    fn check_compressed(&self, compressed: Compressed) -> darling::Result<()> {
+        if !cfg!(feature = "compress") {
+            return Err(darling::Error::custom(
+                "`compressed` requires the `compress` feature of tiberius_row",
+            )
+            .with_span(&self.ident));
+        }
+        let bytes = match &self.try_from {
+            Some(ty) => is_bytes(ty),
+            None => is_bytes(&self.ty) && !self.custom_decode(),
+        };
+        if matches!(compressed, Compressed::Bytes) && self.encoding.is_none() && !bytes {
+            return Err(darling::Error::custom(
+                "`compressed` gives bytes, for a `Vec<u8>` field or `encoding`; \
+                 read text with `compressed = \"utf-16\"` or `compressed = \"utf-8\"`",
+            )
+            .with_span(&self.ident));
+        }
+        Ok(())
+    }
+
+    /// The expression decoding the field from `cells`, with its entry at
+    /// `index` in the struct's table.
+    fn decode(&self, index: usize) -> darling::Result<TokenStream> {
+        let decode = self.decode_column(index)?;
+        Ok(match &self.default {
+            Some(default) => quote! {
+                if ::tiberius_row::__private::has_column(&cells, &Self::__ROW_EXT_FIELDS[#index]) {
+                    #decode
+                } else {
+                    ::std::result::Result::Ok(#default)
+                }
+            },
+            None => decode,
+        })
+    }
+
+    /// [`decode`](Self::decode) for a row that has the column.
+    fn decode_column(&self, index: usize) -> darling::Result<TokenStream> {
+        let field = quote! { &Self::__ROW_EXT_FIELDS[#index] };
+        if self.borrows() {
+            if self.custom_decode() || self.normalizes() || self.tz.is_some() {
+                return Err(
+                    darling::Error::custom("borrowed fields only support `rename`")
+                        .with_span(&self.ident),
+                );
+            }
+            return Ok(quote! { ::tiberius_row::__private::decode_field_ref(&cells, #field) });
+        }
+        let json = self.json || self.json_path.is_some();
+        let split = self.split.is_some();
+        let convert = self.from_str || self.try_from.is_some();
+        if [
+            json, self.xml, self.serde, split, convert, self.wkt, self.wkb,
+        ]
+        .iter()
+        .filter(|&&f| f)
+        .count()
+            > 1
+        {
+            return Err(darling::Error::custom(
+                "only one of `json`, `xml`, `serde`, `split`, `from_str`, `try_from`, `wkt` and `wkb` can be used",
+            )
+            .with_span(&self.ident));
+        }
+        if self.split.as_deref() == Some("") {
+            return Err(
+                darling::Error::custom("the `split` separator cannot be empty")
+                    .with_span(&self.ident),
+            );
+        }
+        if !split && self.skip_empty {
+            return Err(
+                darling::Error::custom("`skip_empty` requires `split`").with_span(&self.ident)
+            );
+        }
+        if let Some(compressed) = self.compressed {
+            self.check_compressed(compressed)?;
+        }
+        if let Some(label) = &self.encoding {
+            check_encoding(label)?;
+        }
+        if self.lossy && self.encoding.is_none() {
+            return Err(
+                darling::Error::custom("`lossy` requires `encoding`").with_span(&self.ident)
+            );
+        }
+        let trim = self.trim.map(Trim::tokens);
+        let decode = if json {
+            let path = match &self.json_path {
+                Some(path) => quote! { Some(#path) },
+                None => quote! { None },
+            };
+            quote! { ::tiberius_row::__private::decode_field_json(&cells, #field, #path) }
+        } else if let Some(sep) = &self.split {
+            let trim = match trim {
+                Some(trim) => quote! { Some(#trim) },
+                None => quote! { None },
+            };
+            let skip_empty = self.skip_empty;
+            quote! {
+                ::tiberius_row::__private::decode_field_split(
+                    &cells,
+                    #field,
+                    &::tiberius_row::__private::split::Split {
+                        sep: #sep,
+                        trim: #trim,
+                        skip_empty: #skip_empty,
+                    },
+                )
+            }
+        } else if convert {
+            let convert = match &self.try_from {
+                Some(ty) => quote! { ::tiberius_row::__private::convert::try_from::<#ty, _> },
+                None => quote! { ::tiberius_row::__private::convert::from_str },
+            };
+            // An `Option` field converts only non-NULL values, and is `None`
+            // for a missing column.
+            if is_option(&self.ty) {
+                quote! {
+                    ::tiberius_row::__private::decode_field_with(
+                        &cells,
+                        #field,
+                        || Some(None),
+                        |val| ::tiberius_row::__private::convert::nullable(val, #convert),
+                    )
+                }
+            } else {
+                quote! {
+                    ::tiberius_row::__private::decode_field_with(&cells, #field, || None, #convert)
+                }
+            }
+        } else if self.wkt || self.wkb {
+            let format = if self.wkt {
+                quote! { ::tiberius_row::__private::geo::Format::Wkt }
+            } else {
+                quote! { ::tiberius_row::__private::geo::Format::Wkb }
+            };
+            quote! { ::tiberius_row::__private::decode_field_geo(&cells, #field, #format) }
+        } else if self.xml {
+            quote! { ::tiberius_row::__private::decode_field_xml(&cells, #field) }
+        } else if self.serde {
+            quote! { ::tiberius_row::__private::decode_field_serde(&cells, #field) }
+        } else {
+            quote! { ::tiberius_row::__private::decode_field(&cells, #field) }
+        };
+        Ok(match &self.tz {
+            Some(tz) => {
+                let tz = tz_tokens(tz)?;
+                quote! { ::tiberius_row::__private::with_tz(#tz, || #decode) }
+            }
+            None => decode,
+        })
+    }
+}
+
+/// The time zone of `#[row_ext(tz = "...")]`, checked during expansion and
+/// parsed once, into a static, by the generated code.
+fn tz_tokens(name: &syn::LitStr) -> darling::Result<TokenStream> {
+    #[cfg(feature = "chrono-tz")]
+    {
+        if name.value().parse::<chrono_tz::Tz>().is_err() {
+            return Err(
+                darling::Error::custom(format!("unknown time zone `{}`", name.value()))
+                    .with_span(name),
+            );
+        }
+        Ok(quote! {{
+            static TZ: ::std::sync::OnceLock<::tiberius_row::__private::Tz> =
+                ::std::sync::OnceLock::new();
+            *TZ.get_or_init(|| ::tiberius_row::__private::parse_tz(#name))
+        }})
+    }
+    #[cfg(not(feature = "chrono-tz"))]
+    Err(
+        darling::Error::custom("`tz` requires the `chrono-tz` feature of tiberius_row")
+            .with_span(name),
+    )
+}
+
+/// Checks the label of `#[row_ext(encoding = "...")]`, so an unknown one
+/// fails the build rather than the first non-NULL value.
+pub(crate) fn check_encoding(label: &syn::LitStr) -> darling::Result<()> {
+    #[cfg(feature = "encoding")]
+    {
+        if encoding_rs::Encoding::for_label(label.value().as_bytes()).is_none() {
+            return Err(
+                darling::Error::custom(format!("unknown encoding `{}`", label.value()))
+                    .with_span(label),
+            );
+        }
+        Ok(())
+    }
+    #[cfg(not(feature = "encoding"))]
+    Err(
+        darling::Error::custom("`encoding` requires the `encoding` feature of tiberius_row")
+            .with_span(label),
+    )
+}
+
+/// Whether the field type is a reference, or an `Option` of one.
+fn is_reference(ty: &syn::Type) -> bool {
+    match ty {
+        syn::Type::Reference(_) => true,
+        syn::Type::Path(path) => {
+            let Some(segment) = path.path.segments.last() else {
+                return false;
+            };
+            match &segment.arguments {
+                syn::PathArguments::AngleBracketed(args) if segment.ident == "Option" => {
+                    matches!(args.args.first(), Some(syn::GenericArgument::Type(ty)) if is_reference(ty))
+                }
+                _ => false,
+            }
+        }
+        _ => false,
+    }
+}
+
+/// Whether the field type is written as `Option<...>`.
+fn is_option(ty: &syn::Type) -> bool {
+    match ty {
+        syn::Type::Path(path) => path
+            .path
+            .segments
+            .last()
+            .is_some_and(|segment| segment.ident == "Option"),
+        _ => false,
+    }
+}
+
+/// Whether the field type is `Vec<u8>`, or an `Option` or `Field` of it.
+fn is_bytes(ty: &syn::Type) -> bool {
+    let syn::Type::Path(path) = ty else {
+        return false;
+    };
+    let Some(segment) = path.path.segments.last() else {
+        return false;
+    };
+    let syn::PathArguments::AngleBracketed(args) = &segment.arguments else {
+        return false;
+    };
+    let Some(syn::GenericArgument::Type(inner)) = args.args.first() else {
+        return false;
+    };
+    match segment.ident.to_string().as_str() {
+        "Option" | "Field" => is_bytes(inner),
+        "Vec" => matches!(inner, syn::Type::Path(inner) if inner.path.is_ident("u8")),
+        _ => false,
+    }
+}
+
+/// `#[row_ext(trim)]` trims both ends, `trim = "left"` or `trim = "right"`
+/// only one.
+#[derive(Debug, Clone, Copy)]
+pub(crate) enum Trim {
+    Left,
+    Right,
+    Both,
+}
+
+impl Trim {
+    fn tokens(self) -> TokenStream {
+        match self {
+            Trim::Left => quote! { ::tiberius_row::__private::Trim::Left },
+            Trim::Right => quote! { ::tiberius_row::__private::Trim::Right },
+            Trim::Both => quote! { ::tiberius_row::__private::Trim::Both },
+        }
+    }
+}
+
+impl FromMeta for Trim {
+    fn from_word() -> darling::Result<Self> {
+        Ok(Trim::Both)
+    }
+
+    fn from_string(value: &str) -> darling::Result<Self> {
+        match value {
+            "left" => Ok(Trim::Left),
+            "right" => Ok(Trim::Right),
+            "both" => Ok(Trim::Both),
+            _ => Err(darling::Error::unknown_value(value)),
+        }
+    }
+}
+
+/// `#[row_ext(compressed)]` keeps the decompressed bytes, while
+/// `compressed = "utf-8"` or `compressed = "utf-16"` reads them as text.
+#[derive(Debug, Clone, Copy)]
+pub(crate) enum Compressed {
+    Bytes,
+    Utf8,
+    Utf16,
+}
+
+impl Compressed {
+    pub(crate) fn tokens(self) -> TokenStream {
+        match self {
+            Compressed::Bytes => quote! { ::tiberius_row::__private::Compressed::Bytes },
+            Compressed::Utf8 => quote! { ::tiberius_row::__private::Compressed::Utf8 },
+            Compressed::Utf16 => quote! { ::tiberius_row::__private::Compressed::Utf16 },
+        }
+    }
+}
+
+impl FromMeta for Compressed {
+    fn from_word() -> darling::Result<Self> {
+        Ok(Compressed::Bytes)
+    }
+
+    fn from_string(value: &str) -> darling::Result<Self> {
+        match value {
+            "utf-8" => Ok(Compressed::Utf8),
+            "utf-16" => Ok(Compressed::Utf16),
+            _ => Err(darling::Error::unknown_value(value)),
+        }
+    }
+}
+
+/// `#[row_ext(bool_from = ["Y", "N"])]`: the strings for true and false.
+#[derive(Debug)]
+pub(crate) struct BoolFrom(pub(crate) String, pub(crate) String);
+
+impl FromMeta for BoolFrom {
+    fn from_expr(expr: &syn::Expr) -> darling::Result<Self> {
+        let values = Vec::<syn::LitStr>::from_expr(expr)?;
+        match values.as_slice() {
+            [yes, no] => Ok(BoolFrom(yes.value(), no.value())),
+            _ => Err(darling::Error::custom(
+                "expected the strings for true and false, like `[\"Y\", \"N\"]`",
+            )
+            .with_span(expr)),
+        }
+    }
+}
+
+pub(crate) fn process_row_ext(input: DeriveInput) -> TokenStream {
+    let StructData {
+        ident,
+        generics,
+        attrs,
+        data,
+        tz,
+        update,
+    } = match StructData::from_derive_input(&input) {
+        Ok(data) => data,
+        Err(e) => return e.write_errors(),
+    };
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let mut fields = data.take_struct().unwrap().fields;
+
+    let mut errors = darling::Error::accumulator();
+    let container = SerdeAttrs::parse(
+        &attrs,
+        Direction::Deserialize,
+        "Row_Ext",
+        UNSUPPORTED_SERDE_CONTAINER,
+    );
+    if let Some(container) = errors.handle(container.map_err(darling::Error::from)) {
+        for f in &mut fields {
+            errors.handle(f.resolve_serde(&container).map_err(darling::Error::from));
+        }
+    }
+    let table = fields
+        .iter()
+        .filter(|f| f.reads_column())
+        .enumerate()
+        .map(|(index, f)| f.field(index))
+        .collect::<Vec<_>>();
+    let mut extras = fields.iter().filter(|f| f.extra).skip(1);
+    if let Some(f) = extras.next() {
+        errors.push(darling::Error::custom("only one field can be `extra`").with_span(&f.ident));
+    }
+    let mut index = 0;
+    let decodes = fields
+        .iter()
+        .filter_map(|f| {
+            let decode = if f.extra {
+                quote! { ::tiberius_row::__private::decode_extra(&cells, Self::__ROW_EXT_FIELDS) }
+            } else if f.skip {
+                let default = f
+                    .default
+                    .clone()
+                    .unwrap_or_else(|| quote! { ::std::default::Default::default() });
+                quote! {
+                    ::std::result::Result::<_, ::tiberius_row::__private::anyhow::Error>::Ok(
+                        #default,
+                    )
+                }
+            } else {
+                index += 1;
+                errors.handle(f.decode(index - 1))?
+            };
+            Some((f, decode))
+        })
+        .collect::<Vec<_>>();
+    let tz = tz.and_then(|tz| errors.handle(tz_tokens(&tz)));
+    if let Err(e) = errors.finish() {
+        return e.write_errors();
+    }
+    let with_tz = |body: TokenStream| match &tz {
+        Some(tz) => quote! { ::tiberius_row::__private::with_tz(#tz, || #body) },
+        None => body,
+    };
+    let inits = decodes.iter().map(|(f, decode)| {
+        let name = &f.ident;
+        quote! { #name: #decode? }
+    });
+    let body = with_tz(quote! {
+        Ok(Self {
+            #(#inits,)*
+        })
+    });
+
+    // Borrowed fields tie the cells to the struct's lifetime, and the struct
+    // can then only be decoded from a row that outlives it.
+    let borrowed = fields.iter().any(FieldData::borrows);
+    let (lt, lt_decl) = if borrowed {
+        match generics.lifetimes().next() {
+            Some(param) => (param.lifetime.clone(), None),
+            None => {
+                return darling::Error::custom("borrowed fields need a lifetime on the struct")
+                    .with_span(&ident)
+                    .write_errors();
+            }
+        }
+    } else {
+        let lt = syn::Lifetime::new("'r", proc_macro2::Span::call_site());
+        (lt.clone(), Some(quote! { <#lt> }))
+    };
+    let from_row = (!borrowed).then(|| {
+        quote! {
+            pub fn from_row(
+                row: ::tiberius_row::__private::tiberius::Row,
+            ) -> Result<Self, ::tiberius_row::__private::anyhow::Error> {
+                Self::from_cells(row.cells())
+            }
+        }
+    });
+    let update = update.then(|| update_from_cells(&decodes, with_tz, &lt, &lt_decl));
+
+    // `FromRow` decodes from the cells of any row, so borrowing structs
+    // decode in `from_cells` itself.
+    let (decode, from_row_impl) = if borrowed {
+        (body, None)
+    } else {
+        let from_row_impl = quote! {
+            impl #impl_generics ::tiberius_row::FromRow for #ident #ty_generics #where_clause {
+                const FIELDS: &'static [::tiberius_row::__private::Field<'static>] =
+                    Self::__ROW_EXT_FIELDS;
+
+                fn decode_row(
+                    cells: &::tiberius_row::__private::Cells<'_, '_>,
+                ) -> Result<Self, ::tiberius_row::__private::anyhow::Error> {
+                    #body
+                }
+            }
+        };
+        (
+            quote! { <Self as ::tiberius_row::FromRow>::decode_row(&cells) },
+            Some(from_row_impl),
+        )
+    };
+
+    quote! {
+        #from_row_impl
+
+        impl #impl_generics #ident #ty_generics #where_clause {
+            const __ROW_EXT_FIELDS: &'static [::tiberius_row::__private::Field<'static>] =
+                &[#(#table),*];
+
+            #from_row
+
+            pub fn from_row_ref #lt_decl(
+                row: &#lt ::tiberius_row::__private::tiberius::Row,
+            ) -> Result<Self, ::tiberius_row::__private::anyhow::Error> {
+                Self::from_cells(row.cells())
+            }
+
+            pub fn from_cells #lt_decl(
+                cells: impl IntoIterator<
+                    Item = (
+                        &#lt ::tiberius_row::__private::tiberius::Column,
+                        &#lt ::tiberius_row::__private::tiberius::ColumnData<'static>,
+                    ),
+                >,
+            ) -> Result<Self, ::tiberius_row::__private::anyhow::Error> {
+                let cells = cells.into_iter().collect::<Vec<_>>();
+                let cells = ::tiberius_row::__private::Cells::new(&cells);
+                #decode
+            }
+
+            #update
+        }
+    }
+}
+
+/// `update_from_row` and `update_from_cells`, which decode the fields whose
+/// columns are in the row before assigning any of them, so a failure leaves
+/// the struct untouched. `extra` and skipped fields are left as they are.
+fn update_from_cells(
+    decodes: &[(&FieldData, TokenStream)],
+    with_tz: impl Fn(TokenStream) -> TokenStream,
+    lt: &syn::Lifetime,
+    lt_decl: &Option<TokenStream>,
+) -> TokenStream {
+    let decodes = decodes
+        .iter()
+        .filter(|(f, _)| f.reads_column())
+        .collect::<Vec<_>>();
+    let names = decodes
+        .iter()
+        .map(|(f, _)| f.ident.as_ref().unwrap())
+        .collect::<Vec<_>>();
+    let vars = names
+        .iter()
+        .map(|name| quote::format_ident!("__row_ext_{}", name))
+        .collect::<Vec<_>>();
+    let values = decodes.iter().enumerate().map(|(index, (_, decode))| {
+        quote! {
+            if ::tiberius_row::__private::has_column(&cells, &Self::__ROW_EXT_FIELDS[#index]) {
+                Some(#decode?)
+            } else {
+                None
+            }
+        }
+    });
+    let types = decodes.iter().map(|(f, _)| &f.ty);
+    let values = with_tz(quote! {
+        ::std::result::Result::<_, ::tiberius_row::__private::anyhow::Error>::Ok((#(#values,)*))
+    });
+    let fields = names.iter().map(|name| name.to_string());
+    quote! {
+        pub fn update_from_row #lt_decl(
+            &mut self,
+            row: &#lt ::tiberius_row::__private::tiberius::Row,
+        ) -> Result<::tiberius_row::ChangedFields, ::tiberius_row::__private::anyhow::Error> {
+            self.update_from_cells(row.cells())
+        }
+
+        pub fn update_from_cells #lt_decl(
+            &mut self,
+            cells: impl IntoIterator<
+                Item = (
+                    &#lt ::tiberius_row::__private::tiberius::Column,
+                    &#lt ::tiberius_row::__private::tiberius::ColumnData<'static>,
+                ),
+            >,
+        ) -> Result<::tiberius_row::ChangedFields, ::tiberius_row::__private::anyhow::Error> {
+            let cells = cells.into_iter().collect::<Vec<_>>();
+            let cells = ::tiberius_row::__private::Cells::new(&cells);
+            let (#(#vars,)*): (#(::std::option::Option<#types>,)*) = #values?;
+            let mut changed = ::tiberius_row::ChangedFields::default();
+            #(
+                if let Some(value) = #vars {
+                    if self.#names != value {
+                        self.#names = value;
+                        changed.push(#fields);
+                    }
+                }
+            )*
+            Ok(changed)
+        }
+    }
+}
@@ -0,0 +1,188 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+
+/// Which half of serde a derive stands in for: `Row_Ext` reads fields like
+/// `Deserialize`, `ToParams` writes them like `Serialize`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Direction {
+    Serialize,
+    Deserialize,
+}
+
+/// The `#[serde(...)]` options that name a field's column or provide its
+/// value, so structs written for serde keep working with the derives.
+#[derive(Debug, Default)]
+pub(crate) struct SerdeAttrs {
+    pub(crate) rename: Option<String>,
+    pub(crate) rename_all: Option<RenameRule>,
+    pub(crate) aliases: Vec<String>,
+    /// `default` or `default = "path"`, as the expression producing the value.
+    pub(crate) default: Option<TokenStream>,
+    /// `skip`, or `skip_serializing` or `skip_deserializing` for the
+    /// derive's direction.
+    pub(crate) skip: bool,
+}
+
+impl SerdeAttrs {
+    /// Reads the `#[serde]` attributes among `attrs`. The options in
+    /// `unsupported` change what serde does in a way `derive` can't follow
+    /// and are rejected; any other option is ignored.
+    pub(crate) fn parse(
+        attrs: &[syn::Attribute],
+        direction: Direction,
+        derive: &str,
+        unsupported: &[&str],
+    ) -> syn::Result<Self> {
+        let mut serde = SerdeAttrs::default();
+        for attr in attrs.iter().filter(|a| a.path().is_ident("serde")) {
+            attr.parse_nested_meta(|meta| {
+                let option = meta
+                    .path
+                    .get_ident()
+                    .map(ToString::to_string)
+                    .unwrap_or_default();
+                if unsupported.contains(&option.as_str()) {
+                    return Err(meta.error(format!(
+                        "`#[serde({})]` is not supported by `{}`",
+                        option, derive
+                    )));
+                }
+                match option.as_str() {
+                    "rename" => {
+                        if let Some(s) = directed(&meta, direction)? {
+                            serde.rename = Some(s.value());
+                        }
+                    }
+                    "rename_all" => {
+                        if let Some(s) = directed(&meta, direction)? {
+                            serde.rename_all = Some(RenameRule::from_lit(&s)?);
+                        }
+                    }
+                    "alias" => {
+                        let s: syn::LitStr = meta.value()?.parse()?;
+                        if direction == Direction::Deserialize {
+                            serde.aliases.push(s.value());
+                        }
+                    }
+                    "default" if direction == Direction::Deserialize => {
+                        serde.default = Some(if meta.input.peek(syn::Token![=]) {
+                            let s: syn::LitStr = meta.value()?.parse()?;
+                            let path: syn::ExprPath = s.parse()?;
+                            quote! { #path() }
+                        } else {
+                            quote! { ::std::default::Default::default() }
+                        });
+                    }
+                    "skip" => serde.skip = true,
+                    "skip_serializing" if direction == Direction::Serialize => serde.skip = true,
+                    "skip_deserializing" if direction == Direction::Deserialize => {
+                        serde.skip = true
+                    }
+                    _ => skip_value(&meta)?,
+                }
+                Ok(())
+            })?;
+        }
+        Ok(serde)
+    }
+}
+
+/// The name in `rename = "..."`, or the one for `direction` in
+/// `rename(serialize = "...", deserialize = "...")`.
+fn directed(
+    meta: &syn::meta::ParseNestedMeta,
+    direction: Direction,
+) -> syn::Result<Option<syn::LitStr>> {
+    if !meta.input.peek(syn::token::Paren) {
+        return meta.value()?.parse().map(Some);
+    }
+    let mut value = None;
+    meta.parse_nested_meta(|nested| {
+        let s: syn::LitStr = nested.value()?.parse()?;
+        let wanted = match direction {
+            Direction::Serialize => "serialize",
+            Direction::Deserialize => "deserialize",
+        };
+        if nested.path.is_ident(wanted) {
+            value = Some(s);
+        }
+        Ok(())
+    })?;
+    Ok(value)
+}
+
+/// Consumes the `= value` or `(...)` of an option, whatever it holds.
+pub(crate) fn skip_value(meta: &syn::meta::ParseNestedMeta) -> syn::Result<()> {
+    if meta.input.peek(syn::Token![=]) {
+        let value = meta.value()?;
+        while !value.is_empty() && !value.peek(syn::Token![,]) {
+            value.parse::<proc_macro2::TokenTree>()?;
+        }
+    } else if meta.input.peek(syn::token::Paren) {
+        meta.input.parse::<proc_macro2::Group>()?;
+    }
+    Ok(())
+}
+
+/// A container's `#[serde(rename_all = "...")]`, applied to the names of
+/// fields without a `rename` of their own as serde does.
+#[derive(Debug, Clone, Copy)]
+pub(crate) enum RenameRule {
+    Lower,
+    Upper,
+    Pascal,
+    Camel,
+    Snake,
+    ScreamingSnake,
+    Kebab,
+    ScreamingKebab,
+}
+
+impl RenameRule {
+    fn from_lit(lit: &syn::LitStr) -> syn::Result<Self> {
+        Ok(match lit.value().as_str() {
+            "lowercase" => RenameRule::Lower,
+            "UPPERCASE" => RenameRule::Upper,
+            "PascalCase" => RenameRule::Pascal,
+            "camelCase" => RenameRule::Camel,
+            "snake_case" => RenameRule::Snake,
+            "SCREAMING_SNAKE_CASE" => RenameRule::ScreamingSnake,
+            "kebab-case" => RenameRule::Kebab,
+            "SCREAMING-KEBAB-CASE" => RenameRule::ScreamingKebab,
+            _ => return Err(syn::Error::new_spanned(lit, "unknown `rename_all` rule")),
+        })
+    }
+
+    /// The name of the snake_case field `field` under the rule.
+    pub(crate) fn apply(self, field: &str) -> String {
+        match self {
+            RenameRule::Lower | RenameRule::Snake => field.to_owned(),
+            RenameRule::Upper | RenameRule::ScreamingSnake => field.to_ascii_uppercase(),
+            RenameRule::Pascal => {
+                let mut pascal = String::with_capacity(field.len());
+                let mut capitalize = true;
+                for c in field.chars() {
+                    if c == '_' {
+                        capitalize = true;
+                    } else if capitalize {
+                        pascal.push(c.to_ascii_uppercase());
+                        capitalize = false;
+                    } else {
+                        pascal.push(c);
+                    }
+                }
+                pascal
+            }
+            RenameRule::Camel => {
+                let pascal = RenameRule::Pascal.apply(field);
+                let mut chars = pascal.chars();
+                match chars.next() {
+                    Some(first) => first.to_ascii_lowercase().to_string() + chars.as_str(),
+                    None => pascal,
+                }
+            }
+            RenameRule::Kebab => field.replace('_', "-"),
+            RenameRule::ScreamingKebab => field.to_ascii_uppercase().replace('_', "-"),
+        }
+    }
+}